@@ -0,0 +1,537 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use poise::serenity_prelude::{self as serenity, ChannelId, CreateEmbedFooter, CreateMessage};
+use tracing::{error, info};
+
+use crate::{
+    announcements::subscriptions::SubscriptionStore,
+    move_store::{diff::CharacterDiff, diff::MoveDiff, refresh::ChangeListener},
+    presentation::{character_embed, decode_move_name},
+    tekken::character::Character,
+};
+
+pub mod subscriptions;
+
+/// Discord rejects messages with more embeds than this
+const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+
+/// Discord rejects messages whose embeds add up to more characters than this
+const MAX_EMBED_CHARACTERS_PER_MESSAGE: usize = 6000;
+
+/// Shorter than Discord allows for a description so a message fits a few characters'
+/// changes, the rest is summed up as "...and N more"
+const MAX_ANNOUNCEMENT_DESCRIPTION_LENGTH: usize = 2000;
+
+/// A message to post in a subscribed channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub channel_id: u64,
+    pub embeds: Vec<AnnouncementEmbed>,
+}
+
+/// Summary of one character's changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementEmbed {
+    pub character: Character,
+    pub title: String,
+    pub description: String,
+    /// How many moves were added, removed and changed, as the description may not list
+    /// them all
+    pub footer: String,
+}
+
+impl From<&CharacterDiff> for AnnouncementEmbed {
+    fn from(diff: &CharacterDiff) -> Self {
+        let mut lines = Vec::new();
+        for m in &diff.added {
+            match &m.name {
+                Some(name) => lines.push(format!("+ `{}` {}", m.id, decode_move_name(name))),
+                None => lines.push(format!("+ `{}`", m.id)),
+            }
+        }
+        for m in &diff.removed {
+            lines.push(format!("- `{}`", m.id));
+        }
+        for change in &diff.changed {
+            let fields = change
+                .fields
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("`{}` {fields}", change.id));
+        }
+
+        let footer = [
+            (diff.added.len(), "added"),
+            (diff.removed.len(), "removed"),
+            (diff.changed.len(), "changed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{count} {what}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        Self {
+            character: diff.character,
+            title: format!("{} frame data changed", diff.character),
+            description: join_lines(&lines),
+            footer,
+        }
+    }
+}
+
+impl AnnouncementEmbed {
+    /// Characters the embed counts towards Discord's limit for a message, which is all
+    /// of its text that gets sent
+    fn len(&self) -> usize {
+        [&self.title, &self.description, &self.footer]
+            .into_iter()
+            .map(|text| text.chars().count())
+            .sum()
+    }
+}
+
+/// Joins lines until the size limit for a description is reached
+fn join_lines(lines: &[String]) -> String {
+    let mut description = String::new();
+    let mut length = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let more = format!("...and {} more", lines.len() - i);
+        let line_length = line.chars().count() + 1;
+        if length + line_length + more.chars().count() > MAX_ANNOUNCEMENT_DESCRIPTION_LENGTH {
+            description.push_str(&more);
+            break;
+        }
+        description.push_str(line);
+        description.push('\n');
+        length += line_length;
+    }
+
+    description.trim_end().to_string()
+}
+
+/// Messages for every subscribed channel with changes to characters it follows. Channels
+/// with many changed characters get several messages
+pub fn build_announcements(
+    diff: &MoveDiff,
+    subscriptions: &[subscriptions::Subscription],
+) -> Vec<Announcement> {
+    subscriptions
+        .iter()
+        .flat_map(|subscription| {
+            let embeds = diff
+                .characters
+                .iter()
+                .filter(|character_diff| subscription.includes(character_diff.character))
+                .map(AnnouncementEmbed::from)
+                .collect::<Vec<_>>();
+
+            split_into_messages(embeds)
+                .into_iter()
+                .map(|embeds| Announcement {
+                    channel_id: subscription.channel_id,
+                    embeds,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Groups embeds into as few messages as Discord's limits on the number of embeds and
+/// their total length allow, keeping their order
+fn split_into_messages(embeds: Vec<AnnouncementEmbed>) -> Vec<Vec<AnnouncementEmbed>> {
+    let mut messages = Vec::new();
+    let mut message: Vec<AnnouncementEmbed> = Vec::new();
+    let mut length = 0;
+
+    for embed in embeds {
+        if message.len() == MAX_EMBEDS_PER_MESSAGE
+            || length + embed.len() > MAX_EMBED_CHARACTERS_PER_MESSAGE
+        {
+            messages.push(std::mem::take(&mut message));
+            length = 0;
+        }
+        length += embed.len();
+        message.push(embed);
+    }
+    if !message.is_empty() {
+        messages.push(message);
+    }
+
+    messages
+}
+
+/// Where announcements get posted
+#[async_trait]
+pub trait AnnouncementSink: Send + Sync {
+    async fn send(&self, announcement: &Announcement) -> Result<()>;
+}
+
+pub struct DiscordAnnouncementSink {
+    http: Arc<serenity::Http>,
+}
+
+impl DiscordAnnouncementSink {
+    pub fn new(http: Arc<serenity::Http>) -> Self {
+        Self { http }
+    }
+}
+
+#[async_trait]
+impl AnnouncementSink for DiscordAnnouncementSink {
+    async fn send(&self, announcement: &Announcement) -> Result<()> {
+        let embeds = announcement
+            .embeds
+            .iter()
+            .map(|embed| {
+                let discord_embed = character_embed(embed.character)
+                    .title(&embed.title)
+                    .description(&embed.description);
+                if embed.footer.is_empty() {
+                    discord_embed
+                } else {
+                    discord_embed.footer(CreateEmbedFooter::new(&embed.footer))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        ChannelId::new(announcement.channel_id)
+            .send_message(&self.http, CreateMessage::new().embeds(embeds))
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Posts the changes found by a refresh to the subscribed channels
+pub struct AnnouncementService<S> {
+    subscriptions: Arc<SubscriptionStore>,
+    sink: S,
+}
+
+impl<S: AnnouncementSink> AnnouncementService<S> {
+    pub fn new(subscriptions: Arc<SubscriptionStore>, sink: S) -> Self {
+        Self {
+            subscriptions,
+            sink,
+        }
+    }
+
+    /// A channel that can't be posted to doesn't stop the others from getting their
+    /// announcements
+    pub async fn announce(&self, diff: &MoveDiff) {
+        let subscriptions = self.subscriptions.subscriptions().await;
+        for announcement in build_announcements(diff, &subscriptions) {
+            match self.sink.send(&announcement).await {
+                Ok(()) => info!("Announced changes in channel {}", announcement.channel_id),
+                Err(e) => error!(
+                    "Failed to announce changes in channel {}: {:?}",
+                    announcement.channel_id, e
+                ),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S: AnnouncementSink> ChangeListener for AnnouncementService<S> {
+    async fn moves_changed(&self, diff: &MoveDiff) {
+        self.announce(diff).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use anyhow::bail;
+    use tokio::sync::Mutex;
+
+    use super::{subscriptions::Subscription, *};
+    use crate::{move_store::MoveMap, tekken::character_move::CharacterMove};
+
+    fn simple_move(id: &str) -> CharacterMove {
+        CharacterMove {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    fn patch_diff() -> MoveDiff {
+        let before = MoveMap::from([
+            (
+                Character::Paul,
+                vec![
+                    CharacterMove {
+                        on_block: Some("-15".into()),
+                        ..simple_move("Paul-qcf+2")
+                    },
+                    simple_move("Paul-b+1"),
+                ],
+            ),
+            (Character::Kazuya, vec![simple_move("Kazuya-1")]),
+            (Character::Lee, vec![simple_move("Lee-1")]),
+        ]);
+        let after = MoveMap::from([
+            (
+                Character::Paul,
+                vec![CharacterMove {
+                    on_block: Some("-13".into()),
+                    ..simple_move("Paul-qcf+2")
+                }],
+            ),
+            (
+                Character::Kazuya,
+                vec![
+                    simple_move("Kazuya-1"),
+                    CharacterMove {
+                        name: Some("Twin Pistons".into()),
+                        startup_frames: Some("i15".into()),
+                        ..simple_move("Kazuya-df+1,2")
+                    },
+                ],
+            ),
+            (Character::Lee, vec![simple_move("Lee-1")]),
+        ]);
+
+        MoveDiff::between(&before, &after)
+    }
+
+    fn subscription(channel_id: u64, characters: &[Character]) -> Subscription {
+        Subscription {
+            channel_id,
+            characters: (!characters.is_empty()).then(|| BTreeSet::from_iter(characters.to_vec())),
+        }
+    }
+
+    #[test]
+    fn test_summarizes_changed_moves() {
+        let announcements = build_announcements(&patch_diff(), &[subscription(1, &[])]);
+
+        assert_eq!(
+            announcements,
+            vec![Announcement {
+                channel_id: 1,
+                embeds: vec![
+                    AnnouncementEmbed {
+                        character: Character::Kazuya,
+                        title: "Kazuya frame data changed".into(),
+                        description: "+ `Kazuya-df+1,2` Twin Pistons".into(),
+                        footer: "1 added".into(),
+                    },
+                    AnnouncementEmbed {
+                        character: Character::Paul,
+                        title: "Paul frame data changed".into(),
+                        description: "- `Paul-b+1`\n`Paul-qcf+2` On Block -15 → -13".into(),
+                        footer: "1 removed, 1 changed".into(),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_only_announces_subscribed_characters() {
+        let announcements = build_announcements(
+            &patch_diff(),
+            &[
+                subscription(1, &[Character::Paul]),
+                subscription(2, &[Character::Lee]),
+            ],
+        );
+
+        assert_eq!(announcements.len(), 1);
+        assert_eq!(announcements[0].channel_id, 1);
+        assert_eq!(
+            announcements[0]
+                .embeds
+                .iter()
+                .map(|embed| embed.character)
+                .collect::<Vec<_>>(),
+            vec![Character::Paul]
+        );
+    }
+
+    #[test]
+    fn test_splits_many_characters_into_several_messages() {
+        let diff = MoveDiff {
+            characters: (0..12)
+                .map(|i| CharacterDiff {
+                    character: Character::Paul,
+                    added: vec![simple_move(&format!("Paul-{i}"))],
+                    removed: vec![],
+                    changed: vec![],
+                })
+                .collect(),
+        };
+
+        let announcements = build_announcements(&diff, &[subscription(1, &[])]);
+
+        assert_eq!(
+            announcements
+                .iter()
+                .map(|announcement| announcement.embeds.len())
+                .collect::<Vec<_>>(),
+            vec![10, 2]
+        );
+    }
+
+    #[test]
+    fn test_keeps_messages_under_discords_length_limit() {
+        let diff = MoveDiff {
+            characters: Character::iter()
+                .map(|character| CharacterDiff {
+                    character,
+                    added: (0..500)
+                        .map(|i| simple_move(&format!("{character}-{i}")))
+                        .collect(),
+                    removed: vec![],
+                    changed: vec![],
+                })
+                .collect(),
+        };
+
+        let announcements = build_announcements(&diff, &[subscription(1, &[])]);
+
+        assert!(announcements.len() > 1);
+        assert_eq!(
+            announcements
+                .iter()
+                .map(|announcement| announcement.embeds.len())
+                .sum::<usize>(),
+            Character::iter().count()
+        );
+        for announcement in &announcements {
+            let length = announcement
+                .embeds
+                .iter()
+                .map(AnnouncementEmbed::len)
+                .sum::<usize>();
+            assert!(length <= MAX_EMBED_CHARACTERS_PER_MESSAGE);
+        }
+    }
+
+    #[test]
+    fn test_footers_count_towards_discords_length_limit() {
+        let embed = AnnouncementEmbed {
+            character: Character::Paul,
+            title: "Paul frame data changed".into(),
+            description: "d".repeat(1000),
+            footer: "f".repeat(2000),
+        };
+
+        let messages = split_into_messages(vec![embed.clone(), embed.clone(), embed]);
+
+        assert_eq!(
+            messages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_truncates_long_descriptions() {
+        let diff = CharacterDiff {
+            character: Character::Paul,
+            added: (0..500)
+                .map(|i| simple_move(&format!("Paul-{i}")))
+                .collect(),
+            removed: vec![],
+            changed: vec![],
+        };
+
+        let embed = AnnouncementEmbed::from(&diff);
+
+        assert!(embed.description.chars().count() <= MAX_ANNOUNCEMENT_DESCRIPTION_LENGTH);
+        assert!(embed.description.ends_with(" more"));
+    }
+
+    #[test]
+    fn test_formats_frame_changes() {
+        let before = MoveMap::from([(Character::Paul, vec![simple_move("Paul-1")])]);
+        let after = MoveMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                startup_frames: Some("i10".into()),
+                ..simple_move("Paul-1")
+            }],
+        )]);
+        let diff = MoveDiff::between(&before, &after);
+
+        let embed = AnnouncementEmbed::from(diff.character(Character::Paul).unwrap());
+
+        assert_eq!(embed.description, "`Paul-1` Startup none → i10");
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Mutex<Vec<Announcement>>,
+        failing_channel: Option<u64>,
+    }
+
+    #[async_trait]
+    impl AnnouncementSink for RecordingSink {
+        async fn send(&self, announcement: &Announcement) -> Result<()> {
+            if self.failing_channel == Some(announcement.channel_id) {
+                bail!("missing permissions");
+            }
+            self.sent.lock().await.push(announcement.clone());
+            Ok(())
+        }
+    }
+
+    async fn service(
+        subscriptions: &[Subscription],
+        sink: RecordingSink,
+    ) -> (AnnouncementService<RecordingSink>, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SubscriptionStore::load(dir.path().join("subscriptions.json"))
+            .await
+            .unwrap();
+        for subscription in subscriptions {
+            store.subscribe(subscription.clone()).await.unwrap();
+        }
+
+        (AnnouncementService::new(Arc::new(store), sink), dir)
+    }
+
+    #[tokio::test]
+    async fn test_posts_to_subscribed_channels() {
+        let (service, _dir) = service(
+            &[subscription(1, &[]), subscription(2, &[Character::Kazuya])],
+            RecordingSink::default(),
+        )
+        .await;
+
+        service.moves_changed(&patch_diff()).await;
+
+        let sent = service.sink.sent.lock().await;
+        assert_eq!(
+            sent.iter()
+                .map(|announcement| (announcement.channel_id, announcement.embeds.len()))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failing_channel_does_not_stop_others() {
+        let (service, _dir) = service(
+            &[subscription(1, &[]), subscription(2, &[])],
+            RecordingSink {
+                failing_channel: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        service.moves_changed(&patch_diff()).await;
+
+        let sent = service.sink.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].channel_id, 2);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::{
+    storage::write_atomically,
+    tekken::character::{Character, deserialize_known_character_list},
+};
+
+/// A channel that wants to hear about frame data changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub channel_id: u64,
+    /// `None` subscribes to every character. Characters that were removed from the
+    /// registry are left out when loading, and subscriptions left without any are dropped
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_characters"
+    )]
+    pub characters: Option<BTreeSet<Character>>,
+}
+
+impl Subscription {
+    pub fn includes(&self, character: Character) -> bool {
+        self.characters
+            .as_ref()
+            .is_none_or(|characters| characters.contains(&character))
+    }
+}
+
+fn deserialize_characters<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<BTreeSet<Character>>, D::Error> {
+    #[derive(Deserialize)]
+    struct KnownCharacters(
+        #[serde(deserialize_with = "deserialize_known_character_list")] BTreeSet<Character>,
+    );
+
+    Option::<KnownCharacters>::deserialize(deserializer)
+        .map(|characters| characters.map(|characters| characters.0))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SubscriptionsFile {
+    subscriptions: Vec<Subscription>,
+}
+
+/// Subscriptions kept in a JSON file, which is rewritten on every change so they
+/// survive restarts
+#[derive(Debug)]
+pub struct SubscriptionStore {
+    path: PathBuf,
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl SubscriptionStore {
+    /// Starts without subscriptions if the file doesn't exist yet
+    pub async fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut subscriptions = match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                serde_json::from_slice::<SubscriptionsFile>(&bytes)
+                    .with_context(|| format!("failed to parse {}", path.display()))?
+                    .subscriptions
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+
+        // Every character the channel followed was removed from the registry, it would
+        // never hear about anything again
+        subscriptions.retain(|subscription| {
+            let empty = subscription
+                .characters
+                .as_ref()
+                .is_some_and(BTreeSet::is_empty);
+            if empty {
+                warn!(
+                    "Dropping the subscription of channel {}, none of its characters are known",
+                    subscription.channel_id
+                );
+            }
+            !empty
+        });
+
+        Ok(Self {
+            path,
+            subscriptions: Mutex::new(subscriptions),
+        })
+    }
+
+    pub async fn subscriptions(&self) -> Vec<Subscription> {
+        self.subscriptions.lock().await.clone()
+    }
+
+    /// Replaces the channel's existing subscription, if any
+    pub async fn subscribe(&self, subscription: Subscription) -> Result<()> {
+        let mut subscriptions = self.subscriptions.lock().await;
+        subscriptions.retain(|s| s.channel_id != subscription.channel_id);
+        subscriptions.push(subscription);
+
+        save(&self.path, &subscriptions).await
+    }
+
+    /// Returns whether the channel was subscribed
+    pub async fn unsubscribe(&self, channel_id: u64) -> Result<bool> {
+        let mut subscriptions = self.subscriptions.lock().await;
+        let count = subscriptions.len();
+        subscriptions.retain(|s| s.channel_id != channel_id);
+        if subscriptions.len() == count {
+            return Ok(false);
+        }
+
+        save(&self.path, &subscriptions).await?;
+        Ok(true)
+    }
+}
+
+async fn save(path: &Path, subscriptions: &[Subscription]) -> Result<()> {
+    let file = SubscriptionsFile {
+        subscriptions: subscriptions.to_vec(),
+    };
+    write_atomically(path, &serde_json::to_vec_pretty(&file)?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paul_only(channel_id: u64) -> Subscription {
+        Subscription {
+            channel_id,
+            characters: Some(BTreeSet::from([Character::Paul])),
+        }
+    }
+
+    #[test]
+    fn test_includes() {
+        let everything = Subscription {
+            channel_id: 1,
+            characters: None,
+        };
+
+        assert!(everything.includes(Character::Kazuya));
+        assert!(paul_only(1).includes(Character::Paul));
+        assert!(!paul_only(1).includes(Character::Kazuya));
+    }
+
+    #[tokio::test]
+    async fn test_load_skips_unknown_characters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.json");
+        tokio::fs::write(
+            &path,
+            r#"{"subscriptions":[{"channel_id":1,"characters":["Paul","Gon"]},{"channel_id":2,"characters":null}]}"#,
+        )
+        .await
+        .unwrap();
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+
+        assert_eq!(
+            store.subscriptions().await,
+            vec![
+                paul_only(1),
+                Subscription {
+                    channel_id: 2,
+                    characters: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_drops_subscriptions_to_only_unknown_characters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.json");
+        tokio::fs::write(
+            &path,
+            r#"{"subscriptions":[{"channel_id":1,"characters":["Gon","Bob"]},{"channel_id":2,"characters":["Paul"]}]}"#,
+        )
+        .await
+        .unwrap();
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+
+        assert_eq!(store.subscriptions().await, vec![paul_only(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_starts_empty_without_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let store = SubscriptionStore::load(dir.path().join("subscriptions.json"))
+            .await
+            .unwrap();
+
+        assert!(store.subscriptions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data/subscriptions.json");
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        store.subscribe(paul_only(1)).await.unwrap();
+        store
+            .subscribe(Subscription {
+                channel_id: 2,
+                characters: None,
+            })
+            .await
+            .unwrap();
+        drop(store);
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        assert_eq!(
+            store.subscriptions().await,
+            vec![
+                paul_only(1),
+                Subscription {
+                    channel_id: 2,
+                    characters: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribing_again_replaces_subscription() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SubscriptionStore::load(dir.path().join("subscriptions.json"))
+            .await
+            .unwrap();
+
+        store
+            .subscribe(Subscription {
+                channel_id: 1,
+                characters: None,
+            })
+            .await
+            .unwrap();
+        store.subscribe(paul_only(1)).await.unwrap();
+
+        assert_eq!(store.subscriptions().await, vec![paul_only(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.json");
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        store.subscribe(paul_only(1)).await.unwrap();
+
+        assert!(store.unsubscribe(1).await.unwrap());
+        assert!(!store.unsubscribe(1).await.unwrap());
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        assert!(store.subscriptions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.json");
+        tokio::fs::write(&path, "not json").await.unwrap();
+
+        assert!(SubscriptionStore::load(&path).await.is_err());
+    }
+}
@@ -1,19 +1,29 @@
+use chrono::Utc;
 use poise::{
     command,
-    serenity_prelude::{Colour, CreateEmbed},
+    serenity_prelude::{AutocompleteChoice, CreateEmbed, CreateEmbedFooter},
 };
-use scraper::Html;
 use tracing::{info, instrument};
 
 use anyhow::Result;
 
 use crate::{
-    Context, Error,
-    converters::okizeme::to_okizeme_url,
+    BotState, Context, Error,
+    commands::{MAX_AUTOCOMPLETE_CHOICES, move_list::reply_unavailable},
+    converters::{okizeme::to_okizeme_url, wavu::to_wavu_url},
     matchers::CharacterMoveMatch,
-    tekken::{TEKKEN_RED, character::Character, character_move::CharacterMove},
+    presentation::{character_embed, decode_move_name},
+    tekken::{
+        character::{Character, normalize_name},
+        character_move::{CharacterMove, MoveField},
+        crush::Crush,
+        frames::FrameField,
+        tracking::TrackingSide,
+    },
 };
 
+const OVERRIDE_MARKER: &str = "✎";
+
 async fn reply_with_move_info(
     ctx: Context<'_>,
     move_info: Option<CharacterMoveMatch>,
@@ -26,7 +36,20 @@ async fn reply_with_move_info(
 
     info!("Found move {}", &info.character_move.id);
 
-    let embed = build_embed_for_move_info(info.character, &info.character_move);
+    let stale_note = ctx
+        .data()
+        .frame_service
+        .stale_data_since()
+        .map(|fetched_at| {
+            format!(
+                "Wavu is unreachable, showing data from {}",
+                fetched_at.format("%Y-%m-%d %H:%M UTC")
+            )
+        });
+    let mut embed = build_embed_for_move_info(info.character, &info.character_move);
+    if let Some(footer) = move_footer(&info.character_move, stale_note) {
+        embed = embed.footer(footer);
+    }
     let reply = poise::CreateReply::default().embed(embed);
     ctx.send(reply).await?;
 
@@ -41,432 +64,299 @@ fn build_embed_for_move_info(character: Character, move_info: &CharacterMove) ->
         title.push_str(&format!(" ({decoded_name})"));
     }
 
-    CreateEmbed::new()
+    let mut description = format!("[okizeme.gg]({})", to_okizeme_url(character, move_info));
+    if let Some(wavu_url) = to_wavu_url(move_info) {
+        description.push_str(&format!(" · [Wavu]({wavu_url})"));
+    }
+    if !move_info.overridden.is_empty() {
+        description.push_str(&format!(
+            "\n{OVERRIDE_MARKER} Community correction of Wavu data"
+        ));
+    }
+
+    character_embed(character)
         .title(title)
-        .description(format!(
-            "[okizeme.gg]({})",
-            to_okizeme_url(character, move_info)
-        ))
-        .colour(Colour::new(TEKKEN_RED))
-        .thumbnail(character.portrait_url())
+        .description(description)
         .fields(vec![
             (
-                "Hit Level",
-                move_info.target.as_deref().unwrap_or_default(),
-                true,
-            ),
-            (
-                "Damage",
-                move_info.damage.as_deref().unwrap_or_default(),
+                field_name(move_info, MoveField::HitLevel),
+                format_hit_level(move_info),
                 true,
             ),
             (
-                "Startup",
-                move_info.startup_frames.as_deref().unwrap_or_default(),
+                field_name(move_info, MoveField::Damage),
+                format_damage(move_info),
                 true,
             ),
+            frame_field(move_info, FrameField::Startup),
         ])
         .fields(vec![
-            (
-                "On Hit",
-                move_info.on_hit.as_deref().unwrap_or_default(),
-                true,
-            ),
-            (
-                "On Block",
-                move_info.on_block.as_deref().unwrap_or_default(),
-                true,
-            ),
-            (
-                "On Counter Hit",
-                move_info.on_counter_hit.as_deref().unwrap_or_default(),
-                true,
-            ),
+            frame_field(move_info, FrameField::OnHit),
+            frame_field(move_info, FrameField::OnBlock),
+            frame_field(move_info, FrameField::OnCounterHit),
         ])
-        .fields(vec![("Notes", format_notes(&move_info.notes), false)])
-}
-
-fn decode_move_name(move_name: &str) -> String {
-    let decoded = html_escape::decode_html_entities(move_name);
-    Html::parse_fragment(&decoded)
-        .root_element()
-        .text()
-        .collect()
-}
-
-fn format_notes(notes: &[String]) -> String {
-    notes
-        .iter()
-        .map(|s| format!("* {s}"))
-        .collect::<Vec<String>>()
-        .join("\n")
-}
-
-async fn character_command_inner(
-    ctx: Context<'_>,
-    character: Character,
-    query: Vec<String>,
-) -> Result<(), Error> {
-    let move_info = ctx.data().frame_service.query_move(character, &query);
-    reply_with_move_info(ctx, move_info).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn alisa(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Alisa, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn anna(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Anna, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("ak"))]
-pub async fn armorking(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::ArmorKing, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn asuka(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Asuka, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn azucena(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Azucena, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn bryan(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Bryan, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn claudio(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Claudio, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn clive(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Clive, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("dj"))]
-pub async fn deviljin(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::DevilJin, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("drag"))]
-pub async fn dragunov(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Dragunov, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn eddy(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Eddy, query).await
-}
-
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("fahk"))]
-pub async fn fahkumram(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Fahkumram, query).await
+        .fields(tracking_fields(move_info))
+        .fields(crush_fields(move_info))
+        .fields(vec![(
+            field_name(move_info, MoveField::Notes),
+            format_notes(&move_info.notes),
+            false,
+        )])
+}
+
+/// Where the move's data came from, followed by a note about the data as a whole
+fn move_footer(move_info: &CharacterMove, note: Option<String>) -> Option<CreateEmbedFooter> {
+    let provenance = move_info.provenance.summary(Utc::now());
+    let text = [Some(provenance), note]
+        .into_iter()
+        .flatten()
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (!text.is_empty()).then(|| CreateEmbedFooter::new(text))
+}
+
+/// Fields set by a community override are marked so it's clear the value isn't Wavu's
+fn field_name(move_info: &CharacterMove, field: MoveField) -> String {
+    if move_info.is_overridden(field) {
+        format!("{field} {OVERRIDE_MARKER}")
+    } else {
+        field.to_string()
+    }
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn feng(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Feng, query).await
+fn frame_field(move_info: &CharacterMove, field: FrameField) -> (String, String, bool) {
+    (
+        field_name(move_info, MoveField::Frames(field)),
+        move_info.frames(field).unwrap_or_default().to_string(),
+        true,
+    )
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("hei"))]
-pub async fn heihachi(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Heihachi, query).await
+fn format_damage(move_info: &CharacterMove) -> String {
+    match move_info.parse_damage() {
+        Some(Ok(damage)) => damage.to_string(),
+        _ => move_info.damage.clone().unwrap_or_default(),
+    }
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("hwo"))]
-pub async fn hwoarang(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Hwoarang, query).await
-}
+/// Strings also get a summary of where the lows are and which hits can be ducked
+fn format_hit_level(move_info: &CharacterMove) -> String {
+    let raw = move_info.target.clone().unwrap_or_default();
+    let Some(Ok(hit_levels)) = move_info.hit_levels() else {
+        return raw;
+    };
+    if hit_levels.0.len() < 2 {
+        return raw;
+    }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("jack"))]
-pub async fn jack8(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Jack8, query).await
-}
+    let mut summary = vec![raw];
+    let low_hits = hit_levels.low_hits();
+    if !low_hits.is_empty() {
+        summary.push(format!("Low on hit {}", format_hit_numbers(&low_hits)));
+    }
+    let duckable_hits = hit_levels.duckable_hits();
+    if !duckable_hits.is_empty() {
+        summary.push(format!(
+            "Duckable on hit {}",
+            format_hit_numbers(&duckable_hits)
+        ));
+    }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn jin(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Jin, query).await
+    summary.join("\n")
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn jun(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Jun, query).await
+fn format_hit_numbers(hits: &[usize]) -> String {
+    hits.iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("kaz"))]
-pub async fn kazuya(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Kazuya, query).await
-}
+fn crush_fields(move_info: &CharacterMove) -> Vec<(String, String, bool)> {
+    let value = match move_info.crushes() {
+        None => return Vec::new(),
+        Some(Ok(crushes)) => crushes
+            .0
+            .iter()
+            .map(Crush::to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Some(Err(e)) => e.value,
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn king(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::King, query).await
+    vec![(field_name(move_info, MoveField::Crush), value, true)]
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn kuma(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Kuma, query).await
-}
+fn tracking_fields(move_info: &CharacterMove) -> Vec<(String, String, bool)> {
+    let tracking = &move_info.tracking;
+    if !tracking.is_known() {
+        return Vec::new();
+    }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn lars(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Lars, query).await
-}
+    let format_side = |side: Option<&TrackingSide>| {
+        side.map(TrackingSide::to_string)
+            .unwrap_or_else(|| "unknown".into())
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn law(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Law, query).await
+    vec![(
+        field_name(move_info, MoveField::Tracking),
+        format!(
+            "Left: {}\nRight: {}",
+            format_side(tracking.left.as_ref()),
+            format_side(tracking.right.as_ref())
+        ),
+        true,
+    )]
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn lee(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Lee, query).await
+fn format_notes(notes: &[String]) -> String {
+    notes
+        .iter()
+        .map(|s| format!("* {s}"))
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn leo(
+pub async fn character_command_inner(
     ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
+    character: Character,
+    query: Vec<String>,
+    version: Option<String>,
 ) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Leo, query).await
-}
+    let (query, query_version) = split_version(query);
+    if let Some(version) = version.or(query_version) {
+        return reply_with_move_at_version(ctx, character, &query, &version).await;
+    }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn leroy(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Leroy, query).await
-}
+    if !ctx.data().frame_service.is_character_available(character) {
+        return reply_unavailable(ctx, character).await;
+    }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn lidia(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Lidia, query).await
+    let move_info = ctx.data().frame_service.query_move(character, &query);
+    reply_with_move_info(ctx, move_info).await
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn lili(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Lili, query).await
-}
+/// Prefix commands can't have named options, so `+bryan uf4 version:2.00` passes the
+/// version as part of the query
+fn split_version(query: Vec<String>) -> (Vec<String>, Option<String>) {
+    let mut version = None;
+    let query = query
+        .into_iter()
+        .filter(|part| match part.strip_prefix("version:") {
+            Some(v) if !v.is_empty() => {
+                version = Some(v.to_string());
+                false
+            }
+            _ => true,
+        })
+        .collect();
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn nina(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Nina, query).await
+    (query, version)
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("miary", "mz"))]
-pub async fn miaryzo(
+async fn reply_with_move_at_version(
     ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::MiaryZo, query).await
-}
+    character: Character,
+    query: &[String],
+    version: &str,
+) -> Result<(), Error> {
+    let frame_service = &ctx.data().frame_service;
+    let Some((version, move_info)) = frame_service
+        .query_move_at_version(character, query, version)
+        .await?
+    else {
+        let versions = frame_service.versions().await;
+        let known = if versions.is_empty() {
+            "No past versions are available".to_string()
+        } else {
+            format!(
+                "Available versions: {}",
+                versions
+                    .iter()
+                    .map(|v| v.label.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        ctx.say(format!("Unknown version \"{version}\". {known}"))
+            .await?;
+        return Ok(());
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn panda(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Panda, query).await
-}
+    let Some(info) = move_info else {
+        ctx.say(format!("No move found in version {}", version.label))
+            .await?;
+        info!("No move was found in version {}", version.label);
+        return Ok(());
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn paul(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Paul, query).await
-}
+    info!(
+        "Found move {} in version {}",
+        &info.character_move.id, version.label
+    );
+
+    let version_note = format!(
+        "Version {}, data from {}",
+        version.label,
+        version.fetched_at.format("%Y-%m-%d")
+    );
+    let mut embed = build_embed_for_move_info(info.character, &info.character_move);
+    if let Some(footer) = move_footer(&info.character_move, Some(version_note)) {
+        embed = embed.footer(footer);
+    }
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn raven(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Raven, query).await
+    Ok(())
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn reina(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Reina, query).await
-}
+/// Command for one character, e.g. `/paul` or `+ak`, with the character's aliases
+pub fn character_command(character: Character) -> poise::Command<BotState, Error> {
+    let name = normalize_name(character.key());
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn shaheen(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Shaheen, query).await
+    let mut command = character_move();
+    command.name = name.clone();
+    command.qualified_name = name.clone();
+    command.identifying_name = name;
+    command.aliases = character.aliases().to_vec();
+    command.description = Some(format!("Look up a move of {character}"));
+    command.custom_data = Box::new(character);
+    command
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn steve(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Steve, query).await
+/// A command for every character in the registry
+pub fn character_commands() -> Vec<poise::Command<BotState, Error>> {
+    Character::iter().map(character_command).collect()
 }
 
+/// Template for the character commands, which set the character as custom data
 #[instrument(skip(ctx))]
 #[command(slash_command, prefix_command)]
-pub async fn victor(
+async fn character_move(
     ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
+    #[description = "Move inputs or move name"]
+    #[autocomplete = "autocomplete_move"]
+    query: Vec<String>,
+    #[description = "Game version to look up, e.g. 2.01"] version: Option<String>,
 ) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Victor, query).await
-}
+    let Some(character) = ctx.command().custom_data.downcast_ref::<Character>() else {
+        return Err(format!("command {} has no character", ctx.command().name).into());
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn xiaoyu(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Xiaoyu, query).await
+    character_command_inner(ctx, *character, query, version).await
 }
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command, aliases("yoshi"))]
-pub async fn yoshimitsu(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Yoshimitsu, query).await
-}
+/// Moves of the command's character closest to what has been typed so far
+async fn autocomplete_move(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let Some(character) = ctx.command().custom_data.downcast_ref::<Character>() else {
+        return Vec::new();
+    };
 
-#[instrument(skip(ctx))]
-#[command(slash_command, prefix_command)]
-pub async fn zafina(
-    ctx: Context<'_>,
-    #[description = "Move inputs or move name"] query: Vec<String>,
-) -> Result<(), Error> {
-    character_command_inner(ctx, Character::Zafina, query).await
+    ctx.data()
+        .frame_service
+        .suggest_moves(*character, partial, MAX_AUTOCOMPLETE_CHOICES)
+        .into_iter()
+        .map(|suggestion| AutocompleteChoice::new(suggestion.label, suggestion.value))
+        .collect()
 }
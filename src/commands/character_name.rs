@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use poise::{
+    PopArgument, SlashArgError, SlashArgument, TooFewArguments,
+    serenity_prelude::{self as serenity, CommandOptionType, CreateCommandOption, ResolvedValue},
+};
+
+use crate::tekken::character::Character;
+
+/// Names are at most a few words, e.g. "Armor King"
+pub const MAX_CHARACTER_NAME_WORDS: usize = 3;
+
+/// Character name as typed by the user. Prefix commands split arguments on spaces, this
+/// takes as many words as make up a character's name so "+crushes armor king high"
+/// reads "armor king"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterName(pub String);
+
+/// The character named by the first words and how many words the name takes. The longest
+/// name wins so "devil jin 1" is Devil Jin and not Jin
+pub fn longest_character_name(words: &[&str]) -> Option<(Character, usize)> {
+    (1..=MAX_CHARACTER_NAME_WORDS.min(words.len()))
+        .rev()
+        .find_map(|count| Some((Character::from_name(&words[..count].join(" "))?, count)))
+}
+
+/// The character name at the start of `args` and the arguments after it. Takes only the
+/// first word if no character is named so the reply can suggest one
+fn split_character_name(args: &str) -> Option<(CharacterName, &str)> {
+    let mut words = Vec::new();
+    let mut rests = Vec::new();
+    let mut rest = args.trim_start();
+    while words.len() < MAX_CHARACTER_NAME_WORDS && !rest.is_empty() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        words.push(rest[..end].trim_matches('"'));
+        rest = rest[end..].trim_start();
+        rests.push(rest);
+    }
+
+    let count = match longest_character_name(&words) {
+        Some((_, count)) => count,
+        None if words.is_empty() => return None,
+        None => 1,
+    };
+
+    Some((CharacterName(words[..count].join(" ")), rests[count - 1]))
+}
+
+#[async_trait]
+impl<'a> PopArgument<'a> for CharacterName {
+    async fn pop_from(
+        args: &'a str,
+        attachment_index: usize,
+        _ctx: &serenity::Context,
+        _msg: &serenity::Message,
+    ) -> Result<(&'a str, usize, Self), (Box<dyn std::error::Error + Send + Sync>, Option<String>)>
+    {
+        let (name, rest) =
+            split_character_name(args).ok_or_else(|| (TooFewArguments::default().into(), None))?;
+
+        Ok((rest, attachment_index, name))
+    }
+}
+
+#[async_trait]
+impl SlashArgument for CharacterName {
+    async fn extract(
+        _ctx: &serenity::Context,
+        _interaction: &serenity::CommandInteraction,
+        value: &ResolvedValue<'_>,
+    ) -> Result<Self, SlashArgError> {
+        match *value {
+            ResolvedValue::String(name) => Ok(CharacterName(name.to_string())),
+            _ => Err(SlashArgError::new_command_structure_mismatch(
+                "expected String",
+            )),
+        }
+    }
+
+    fn create(builder: CreateCommandOption) -> CreateCommandOption {
+        builder.kind(CommandOptionType::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("armor king high", Some(("armor king", "high")))]
+    #[case("ak high", Some(("ak", "high")))]
+    #[case("devil jin -12", Some(("devil jin", "-12")))]
+    #[case("jin  -12", Some(("jin", "-12")))]
+    #[case("\"miary zo\" m,l", Some(("miary zo", "m,l")))]
+    #[case("Miary Zo", Some(("Miary Zo", "")))]
+    #[case("armr king high", Some(("armr", "king high")))]
+    #[case("armor king", Some(("armor king", "")))]
+    #[case("  ", None)]
+    fn test_split_character_name(#[case] args: &str, #[case] expected: Option<(&str, &str)>) {
+        let expected = expected.map(|(name, rest)| (CharacterName(name.into()), rest));
+
+        assert_eq!(split_character_name(args), expected);
+    }
+}
@@ -0,0 +1,35 @@
+use poise::command;
+use tracing::instrument;
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{autocomplete_character, build_embed_for_move_list, character_moves},
+    },
+    tekken::crush::CrushKind,
+};
+
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn crushes(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+    #[description = "Kind of crush"] kind: CrushKind,
+) -> Result<(), Error> {
+    let Some((character, moves)) = character_moves(ctx, &character.0, |character| {
+        ctx.data().frame_service.moves_with_crush(character, kind)
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let title = format!("{character} {}es", kind.to_string().to_lowercase());
+    let embed = build_embed_for_move_list(character, &title, &moves);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use poise::command;
+use tracing::instrument;
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{autocomplete_character, build_embed_for_move_list, character_moves},
+    },
+};
+
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command, rename = "punish")]
+pub async fn punishers(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+    #[description = "How negative the move to punish is on block, e.g. -12"] frames: i32,
+) -> Result<(), Error> {
+    let frames = match frames_to_punish(frames) {
+        Ok(frames) => frames,
+        Err(reply) => {
+            ctx.say(reply).await?;
+            return Ok(());
+        }
+    };
+    let Some((character, moves)) = character_moves(ctx, &character.0, |character| {
+        ctx.data().frame_service.punishers(character, frames)
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let title = format!("{character} punishers at -{frames}");
+    let embed = build_embed_for_move_list(character, &title, &moves);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn lows(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+) -> Result<(), Error> {
+    let Some((character, moves)) = character_moves(ctx, &character.0, |character| {
+        ctx.data().frame_service.lows_by_damage(character)
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let title = format!("{character} lows by damage");
+    let embed = build_embed_for_move_list(character, &title, &moves);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// How many frames the move to punish leaves its user at on block. Moves that are plus
+/// on block can't be punished
+fn frames_to_punish(frames: i32) -> Result<u32, String> {
+    if frames > 0 {
+        return Err(format!(
+            "A move at +{frames} on block can't be punished, did you mean -{frames}?"
+        ));
+    }
+
+    Ok(frames.unsigned_abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(-12, Ok(12))]
+    #[case(0, Ok(0))]
+    #[case(
+        15,
+        Err("A move at +15 on block can't be punished, did you mean -15?".into())
+    )]
+    fn test_frames_to_punish(#[case] frames: i32, #[case] expected: Result<u32, String>) {
+        assert_eq!(frames_to_punish(frames), expected);
+    }
+}
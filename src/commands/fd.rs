@@ -0,0 +1,76 @@
+use poise::command;
+use tracing::{info, instrument};
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_move::character_command_inner,
+        character_name::longest_character_name,
+        move_list::{autocomplete_character, unknown_character_reply},
+    },
+    tekken::character::Character,
+};
+
+/// Frame data of any character's move
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn fd(
+    ctx: Context<'_>,
+    #[description = "Character name or nickname"]
+    #[autocomplete = "autocomplete_character"]
+    character: String,
+    #[description = "Move inputs or move name"]
+    #[rename = "move"]
+    query: Vec<String>,
+) -> Result<(), Error> {
+    // Prefix commands split "+fd armor king df2" after "armor"
+    let words = character
+        .split_whitespace()
+        .map(String::from)
+        .chain(query)
+        .collect::<Vec<_>>();
+
+    let Some((character, query)) = split_character(&words) else {
+        let name = words.first().cloned().unwrap_or_default();
+        info!("Unknown character {name}");
+        ctx.say(unknown_character_reply(&name)).await?;
+        return Ok(());
+    };
+
+    character_command_inner(ctx, character, query, None).await
+}
+
+/// The character named by the first words and the query made of the rest. The longest
+/// name wins so "devil jin 1" is Devil Jin's 1 and not Jin's
+fn split_character(words: &[String]) -> Option<(Character, Vec<String>)> {
+    let names = words.iter().map(String::as_str).collect::<Vec<_>>();
+    let (character, count) = longest_character_name(&names)?;
+
+    Some((character, words[count..].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn words(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[rstest]
+    #[case("armor king df2", Some((Character::ArmorKing, "df2")))]
+    #[case("ak df2", Some((Character::ArmorKing, "df2")))]
+    #[case("devil jin 1", Some((Character::DevilJin, "1")))]
+    #[case("jin 1", Some((Character::Jin, "1")))]
+    #[case("jack-8 jab jackhammer", Some((Character::Jack8, "jab jackhammer")))]
+    #[case("Miary Zo", Some((Character::MiaryZo, "")))]
+    #[case("armr king df2", None)]
+    #[case("", None)]
+    fn test_split_character(#[case] input: &str, #[case] expected: Option<(Character, &str)>) {
+        let expected = expected.map(|(character, query)| (character, words(query)));
+
+        assert_eq!(split_character(&words(input)), expected);
+    }
+}
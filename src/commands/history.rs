@@ -0,0 +1,105 @@
+use poise::{
+    command,
+    serenity_prelude::{Colour, CreateEmbed},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{MAX_DESCRIPTION_LENGTH, autocomplete_character, find_character},
+    },
+    move_store::history::MoveRevision,
+    presentation::character_embed,
+    tekken::TEKKEN_RED,
+};
+
+/// Game versions whose frame data can be looked up
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn versions(ctx: Context<'_>) -> Result<(), Error> {
+    let versions = ctx.data().frame_service.versions().await;
+    if versions.is_empty() {
+        ctx.say("No past versions are available").await?;
+        return Ok(());
+    }
+
+    let list = versions
+        .iter()
+        .rev()
+        .map(|v| format!("**{}** · {}", v.label, v.fetched_at.format("%Y-%m-%d")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let embed = CreateEmbed::new()
+        .title("Game versions")
+        .description(list)
+        .colour(Colour::new(TEKKEN_RED));
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// How a move changed over the game versions
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn history(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+    #[description = "Move inputs or move name"] query: Vec<String>,
+) -> Result<(), Error> {
+    let Some(character) = find_character(ctx, &character.0).await? else {
+        return Ok(());
+    };
+
+    let Some((found, revisions)) = ctx
+        .data()
+        .frame_service
+        .move_revisions(character, &query)
+        .await?
+    else {
+        ctx.say("No move found".to_string()).await?;
+        info!("No move was found");
+        return Ok(());
+    };
+
+    let id = &found.character_move.id;
+    info!("Found {} versions of move {id}", revisions.len());
+
+    let mut lines = revisions
+        .iter()
+        .map(|(version, revision)| {
+            let change = match revision {
+                MoveRevision::Added => "Added".to_string(),
+                MoveRevision::Removed => "Removed".to_string(),
+                MoveRevision::Changed(fields) => fields
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            format!("**{}** {change}", version.label)
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push("No recorded changes".into());
+    }
+
+    // Newest changes matter most, so those are kept when the list is too long
+    let mut description = String::new();
+    for line in lines.iter().rev() {
+        if description.len() + line.len() + 1 > MAX_DESCRIPTION_LENGTH {
+            break;
+        }
+        description.insert_str(0, &format!("{line}\n"));
+    }
+
+    let embed = character_embed(character)
+        .title(format!("History of {id}"))
+        .description(description.trim_end());
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
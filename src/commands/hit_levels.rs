@@ -0,0 +1,45 @@
+use poise::command;
+use tracing::instrument;
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{autocomplete_character, build_embed_for_move_list, character_moves},
+    },
+    tekken::hit_level::HitLevels,
+};
+
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command, rename = "hitlevels")]
+pub async fn hit_levels(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+    #[description = "Hit levels of every hit, e.g. m,l or h-m-l"] levels: String,
+) -> Result<(), Error> {
+    let hit_levels = match HitLevels::parse(&levels) {
+        Ok(hit_levels) => hit_levels,
+        Err(e) => {
+            ctx.say(format!("Invalid hit levels: {}", e.reason)).await?;
+            return Ok(());
+        }
+    };
+
+    let Some((character, moves)) = character_moves(ctx, &character.0, |character| {
+        ctx.data()
+            .frame_service
+            .moves_with_hit_levels(character, &hit_levels)
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let title = format!("{character} moves hitting {hit_levels}");
+    let embed = build_embed_for_move_list(character, &title, &moves);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
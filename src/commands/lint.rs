@@ -0,0 +1,74 @@
+use poise::{
+    command,
+    serenity_prelude::{Colour, CreateAttachment, CreateEmbed},
+};
+use tracing::{info, instrument};
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{MAX_DESCRIPTION_LENGTH, autocomplete_character, character_moves},
+    },
+    tekken::TEKKEN_RED,
+};
+
+/// Check the loaded frame data for problems, the full report is attached as a file
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command, owners_only)]
+pub async fn lint(
+    ctx: Context<'_>,
+    #[description = "Only check this character"]
+    #[autocomplete = "autocomplete_character"]
+    character: Option<CharacterName>,
+) -> Result<(), Error> {
+    let report = match character {
+        Some(name) => {
+            let Some((_, report)) = character_moves(ctx, &name.0, |character| {
+                ctx.data().frame_service.lint_character(character)
+            })
+            .await?
+            else {
+                return Ok(());
+            };
+            report
+        }
+        None => ctx.data().frame_service.lint(),
+    };
+
+    info!("Found {} problems", report.problem_count());
+
+    if report.is_empty() {
+        ctx.say("No problems found").await?;
+        return Ok(());
+    }
+
+    let mut summary = String::new();
+    for (i, lint) in report.characters.iter().enumerate() {
+        let line = format!("{} · {}\n", lint.character, lint.problems.len());
+        let more = format!("...and {} more", report.characters.len() - i);
+        if summary.len() + line.len() + more.len() > MAX_DESCRIPTION_LENGTH {
+            summary.push_str(&more);
+            break;
+        }
+        summary.push_str(&line);
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} problems in {} characters",
+            report.problem_count(),
+            report.characters.len()
+        ))
+        .description(summary)
+        .colour(Colour::new(TEKKEN_RED));
+    let attachment = CreateAttachment::bytes(report.to_string().into_bytes(), "lint.txt");
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(attachment),
+    )
+    .await?;
+
+    Ok(())
+}
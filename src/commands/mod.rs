@@ -1,2 +1,113 @@
+use poise::Command;
+
+use crate::{
+    BotState, Error,
+    commands::{
+        character_move::character_commands,
+        crushes::crushes,
+        damage::{lows, punishers},
+        fd::fd,
+        history::{history, versions},
+        hit_levels::hit_levels,
+        lint::lint,
+        ping::ping,
+        sidestep::sidestep,
+        subscriptions::{subscribe, unsubscribe},
+    },
+};
+
 pub mod character_move;
+pub mod character_name;
+pub mod crushes;
+pub mod damage;
+pub mod fd;
+pub mod history;
+pub mod hit_levels;
+pub mod lint;
+pub mod move_list;
 pub mod ping;
+pub mod sidestep;
+pub mod subscriptions;
+
+/// Most suggestions Discord shows for an autocompleted option
+pub const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
+/// Every command of the bot
+pub fn commands() -> Vec<Command<BotState, Error>> {
+    let mut commands = vec![
+        ping(),
+        sidestep(),
+        hit_levels(),
+        crushes(),
+        punishers(),
+        lows(),
+        versions(),
+        history(),
+        lint(),
+        subscribe(),
+        unsubscribe(),
+        fd(),
+    ];
+    commands.extend(character_commands());
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::tekken::character::Character;
+
+    #[test]
+    fn test_every_character_has_a_command() {
+        let commands = commands();
+
+        for character in Character::iter() {
+            let command = commands
+                .iter()
+                .find(|command| command.custom_data.downcast_ref() == Some(&character))
+                .unwrap_or_else(|| panic!("{character} has no command"));
+            assert_eq!(&command.aliases, character.aliases());
+            assert!(command.parameters[0].autocomplete_callback.is_some());
+        }
+        assert_eq!(
+            commands
+                .iter()
+                .find(|c| c.name == "armorking")
+                .unwrap()
+                .aliases,
+            vec!["ak"]
+        );
+        assert!(commands.iter().any(|c| c.name == "jack8"));
+    }
+
+    #[test]
+    fn test_character_options_suggest_characters() {
+        for command in commands() {
+            for parameter in &command.parameters {
+                if parameter.name == "character" {
+                    assert!(
+                        parameter.autocomplete_callback.is_some(),
+                        "{} doesn't suggest characters",
+                        command.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_command_names_and_aliases_are_unique() {
+        let mut owners = HashMap::new();
+
+        for command in commands() {
+            for name in std::iter::once(&command.name).chain(&command.aliases) {
+                if let Some(owner) = owners.insert(name.to_lowercase(), command.name.clone()) {
+                    panic!("\"{name}\" is used by both {owner} and {}", command.name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use poise::serenity_prelude::CreateEmbed;
+use tracing::info;
+
+use crate::{
+    Context, Error,
+    commands::MAX_AUTOCOMPLETE_CHOICES,
+    presentation::{character_embed, decode_move_name},
+    tekken::{character::Character, character_move::CharacterMove, frames::FrameField},
+};
+
+/// Discord rejects embed descriptions longer than this
+pub const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Character names closest to what has been typed so far
+pub async fn autocomplete_character(_ctx: Context<'_>, partial: &str) -> Vec<String> {
+    Character::closest(partial, MAX_AUTOCOMPLETE_CHOICES)
+        .iter()
+        .map(Character::to_string)
+        .collect()
+}
+
+/// The character called `name`. Replies with the closest names and returns `None` if
+/// there's no such character
+pub async fn find_character(ctx: Context<'_>, name: &str) -> Result<Option<Character>, Error> {
+    let character = Character::from_name(name);
+    if character.is_none() {
+        info!("Unknown character {name}");
+        ctx.say(unknown_character_reply(name)).await?;
+    }
+
+    Ok(character)
+}
+
+/// The character called `name` with the moves `find` picks from their data. Replies and
+/// returns `None` if there's no such character or no data for them
+pub async fn character_moves<T>(
+    ctx: Context<'_>,
+    name: &str,
+    find: impl FnOnce(Character) -> Option<T>,
+) -> Result<Option<(Character, T)>, Error> {
+    let Some(character) = find_character(ctx, name).await? else {
+        return Ok(None);
+    };
+    let Some(moves) = find(character) else {
+        reply_unavailable(ctx, character).await?;
+        return Ok(None);
+    };
+
+    Ok(Some((character, moves)))
+}
+
+pub async fn reply_unavailable(ctx: Context<'_>, character: Character) -> Result<(), Error> {
+    ctx.say(format!("Data for {character} is temporarily unavailable"))
+        .await?;
+    info!("No data available for {character}");
+
+    Ok(())
+}
+
+pub fn unknown_character_reply(name: &str) -> String {
+    let names = Character::similar(name)
+        .iter()
+        .map(Character::to_string)
+        .collect::<Vec<_>>();
+
+    match names.as_slice() {
+        [] => format!("Unknown character \"{name}\""),
+        [only] => {
+            format!("Unknown character \"{name}\". Did you mean {only}?")
+        }
+        [rest @ .., last] => format!(
+            "Unknown character \"{name}\". Did you mean {} or {last}?",
+            rest.join(", ")
+        ),
+    }
+}
+
+/// Embed listing moves by id, name, startup and damage, cut off once Discord's size limit is reached
+pub fn build_embed_for_move_list(
+    character: Character,
+    title: &str,
+    moves: &[CharacterMove],
+) -> CreateEmbed {
+    character_embed(character)
+        .title(format!("{title} ({})", moves.len()))
+        .description(format_move_list(moves))
+}
+
+fn format_move_list(moves: &[CharacterMove]) -> String {
+    if moves.is_empty() {
+        return "No moves found".into();
+    }
+
+    let mut list = String::new();
+    for (i, m) in moves.iter().enumerate() {
+        let mut line = format!("`{}`", m.id);
+        if let Some(name) = &m.name {
+            line.push_str(&format!(" {}", decode_move_name(name)));
+        }
+        if let Some(startup) = m.frames(FrameField::Startup) {
+            line.push_str(&format!(" · {startup}"));
+        }
+        if let Some(Ok(damage)) = m.parse_damage() {
+            line.push_str(&format!(" · {} dmg", damage.total()));
+        }
+        line.push('\n');
+
+        let more = format!("...and {} more", moves.len() - i);
+        if list.len() + line.len() + more.len() > MAX_DESCRIPTION_LENGTH {
+            list.push_str(&more);
+            break;
+        }
+        list.push_str(&line);
+    }
+
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("armr", "Unknown character \"armr\". Did you mean Armor King?")]
+    #[case("xyzzy", "Unknown character \"xyzzy\"")]
+    fn test_unknown_character_reply(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(unknown_character_reply(name), expected);
+    }
+}
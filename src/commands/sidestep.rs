@@ -0,0 +1,37 @@
+use poise::command;
+use tracing::instrument;
+
+use crate::{
+    Context, Error,
+    commands::{
+        character_name::CharacterName,
+        move_list::{autocomplete_character, build_embed_for_move_list, character_moves},
+    },
+    tekken::tracking::SidestepDirection,
+};
+
+#[instrument(skip(ctx))]
+#[command(slash_command, prefix_command)]
+pub async fn sidestep(
+    ctx: Context<'_>,
+    #[description = "Character name"]
+    #[autocomplete = "autocomplete_character"]
+    character: CharacterName,
+    #[description = "Direction to sidestep"] direction: SidestepDirection,
+) -> Result<(), Error> {
+    let Some((character, moves)) = character_moves(ctx, &character.0, |character| {
+        ctx.data()
+            .frame_service
+            .moves_weak_to_sidestep(character, direction)
+    })
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let title = format!("{character} moves weak to sidestep {direction}");
+    let embed = build_embed_for_move_list(character, &title, &moves);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
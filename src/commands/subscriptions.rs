@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use poise::command;
+use tracing::{info, instrument};
+
+use crate::{
+    Context, Error, announcements::subscriptions::Subscription, commands::move_list::find_character,
+};
+
+/// Post frame data changes in this channel
+#[instrument(skip(ctx))]
+#[command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn subscribe(
+    ctx: Context<'_>,
+    #[description = "Comma separated characters, all characters if left empty"]
+    #[rest]
+    characters: Option<String>,
+) -> Result<(), Error> {
+    let characters = match characters {
+        Some(names) => {
+            let mut characters = BTreeSet::new();
+            for name in names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                let Some(character) = find_character(ctx, name).await? else {
+                    return Ok(());
+                };
+                characters.insert(character);
+            }
+            (!characters.is_empty()).then_some(characters)
+        }
+        None => None,
+    };
+
+    let reply = match &characters {
+        Some(characters) => format!(
+            "This channel will be told about frame data changes for {}",
+            characters
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        None => "This channel will be told about all frame data changes".to_string(),
+    };
+
+    let channel_id = ctx.channel_id().get();
+    ctx.data()
+        .subscriptions
+        .subscribe(Subscription {
+            channel_id,
+            characters,
+        })
+        .await?;
+    info!("Subscribed channel {channel_id}");
+
+    ctx.say(reply).await?;
+
+    Ok(())
+}
+
+/// Stop posting frame data changes in this channel
+#[instrument(skip(ctx))]
+#[command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn unsubscribe(ctx: Context<'_>) -> Result<(), Error> {
+    let channel_id = ctx.channel_id().get();
+
+    if ctx.data().subscriptions.unsubscribe(channel_id).await? {
+        info!("Unsubscribed channel {channel_id}");
+        ctx.say("This channel won't be told about frame data changes anymore")
+            .await?;
+    } else {
+        ctx.say("This channel isn't subscribed to frame data changes")
+            .await?;
+    }
+
+    Ok(())
+}
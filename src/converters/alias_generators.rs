@@ -12,7 +12,7 @@ pub fn drop_first_plus_after_letter(character: Character, move_id: &str) -> Cow<
     // Drop the character name, alias matchers don't include the
     // character name when comparing the query
     let chars = {
-        let character_name_prefix = character.to_string() + "-";
+        let character_name_prefix = character.move_id_prefix();
 
         let without_prefix = match move_id.strip_prefix(&character_name_prefix) {
             Some(s) => s,
@@ -1,2 +1,3 @@
 pub mod alias_generators;
 pub mod okizeme;
+pub mod wavu;
@@ -3,7 +3,7 @@ use crate::tekken::{character::Character, character_move::CharacterMove};
 pub fn to_okizeme_url(character: Character, move_info: &CharacterMove) -> String {
     let mut url = String::new();
     url.push_str(OKIZEME_DB_BASE_URL);
-    url.push_str(&to_okizeme_name(character));
+    url.push_str(&character.okizeme_slug());
     url.push('/');
     url.push_str(&to_okizeme_input(character, &move_info.id));
 
@@ -13,21 +13,10 @@ pub fn to_okizeme_url(character: Character, move_info: &CharacterMove) -> String
     url
 }
 
-fn to_okizeme_name(character: Character) -> String {
-    match character {
-        Character::ArmorKing => "armor-king".into(),
-        Character::DevilJin => "devil-jin".into(),
-        Character::MiaryZo => "miary-zo".into(),
-        _ => character.to_string().to_lowercase(),
-    }
-}
-
 fn to_okizeme_input(character: Character, move_id: &str) -> String {
-    let mut name_part = character.to_string();
-    name_part.push('-');
+    let prefix = character.move_id_prefix();
 
-    let move_part = &move_id[name_part.len()..];
-    move_part.into()
+    move_id.strip_prefix(&prefix).unwrap_or(move_id).into()
 }
 
 const OKIZEME_DB_BASE_URL: &str = "https://okizeme.gg/database/";
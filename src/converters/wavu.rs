@@ -0,0 +1,86 @@
+use reqwest::Url;
+
+use crate::tekken::character_move::CharacterMove;
+
+/// Link to the move's section of the wiki page it was read from, if it came from Wavu
+pub fn to_wavu_url(move_info: &CharacterMove) -> Option<String> {
+    let page = move_info.provenance.page.as_ref()?;
+
+    // The wiki uses underscores for spaces in page names and section anchors
+    let mut url = Url::parse(WAVU_PAGE_BASE_URL)
+        .ok()?
+        .join(&page.replace(' ', "_"))
+        .ok()?;
+    url.set_fragment(Some(&section_anchor(&move_info.id)));
+
+    Some(url.to_string())
+}
+
+/// Anchor of a section the way MediaWiki writes it in links: spaces become underscores
+/// and characters that can't be in a URL fragment, such as the `#` of just frame inputs,
+/// become a dot followed by their hex code
+fn section_anchor(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            ' ' => "_".to_string(),
+            '#' | '%' | '"' | '<' | '>' | '`' | '[' | ']' | '{' | '}' | '|' | '\\' | '^' => {
+                format!(".{:02X}", c as u32)
+            }
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+const WAVU_PAGE_BASE_URL: &str = "https://wavu.wiki/t/";
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::tekken::provenance::Provenance;
+
+    #[rstest]
+    #[case(
+        "Paul movelist",
+        "Paul-qcf+2",
+        "https://wavu.wiki/t/Paul_movelist#Paul-qcf+2"
+    )]
+    #[case(
+        "Jack-8 movelist",
+        "Jack-8-1,2",
+        "https://wavu.wiki/t/Jack-8_movelist#Jack-8-1,2"
+    )]
+    #[case(
+        "Armor King movelist",
+        "Armor King-df+1",
+        "https://wavu.wiki/t/Armor_King_movelist#Armor_King-df+1"
+    )]
+    #[case(
+        "Kazuya movelist",
+        "Kazuya-f,n,d,df#2",
+        "https://wavu.wiki/t/Kazuya_movelist#Kazuya-f,n,d,df.232"
+    )]
+    fn test_to_wavu_url(#[case] page: &str, #[case] id: &str, #[case] expected: &str) {
+        let move_info = CharacterMove {
+            id: id.into(),
+            provenance: Provenance {
+                page: Some(page.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(to_wavu_url(&move_info).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_no_url_without_page() {
+        let move_info = CharacterMove {
+            id: "Paul-qcf+2".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(to_wavu_url(&move_info), None);
+    }
+}
@@ -1,29 +1,50 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
 use anyhow::Result;
 use poise::serenity_prelude as serenity;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use crate::commands::character_move::*;
-use crate::commands::ping::ping;
+use crate::announcements::subscriptions::SubscriptionStore;
+use crate::announcements::{AnnouncementService, DiscordAnnouncementSink};
 use crate::matchers::jaro_matcher::JaroMoveMatcher;
-use crate::repositories::wavu_move_repository::WavuMoveRepository;
+use crate::move_store::history::MoveHistoryConfig;
+use crate::move_store::refresh::spawn_periodic_refresh;
+use crate::move_store::{MoveStore, MoveStoreConfig};
+use crate::repositories::MoveRepository;
+use crate::repositories::file_move_repository::FileMoveRepository;
+use crate::repositories::overriding_move_repository::{
+    FileOverrideSource, OverridingMoveRepository,
+};
+use crate::repositories::wavu_client::{WavuClient, WavuClientConfig};
+use crate::repositories::wavu_move_repository::{WAVU_API_URL, WavuMoveRepository};
 use crate::services::frame_service::FrameService;
+use crate::tekken::character_registry::{self, CharacterRegistry, registry};
 
+pub mod announcements;
 pub mod commands;
 pub mod converters;
 pub mod matchers;
 pub mod move_store;
+pub mod presentation;
 pub mod repositories;
 pub mod services;
+pub mod storage;
 pub mod tekken;
 
+const DEFAULT_MOVE_SNAPSHOT_PATH: &str = "data/moves_snapshot.json";
+const DEFAULT_MOVE_REFRESH_INTERVAL_MINUTES: u64 = 6 * 60;
+const DEFAULT_SUBSCRIPTIONS_PATH: &str = "data/subscriptions.json";
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, BotState, Error>;
 
 pub struct BotState {
-    frame_service: FrameService<WavuMoveRepository, JaroMoveMatcher>,
+    frame_service: FrameService<Box<dyn MoveRepository>, JaroMoveMatcher>,
+    subscriptions: Arc<SubscriptionStore>,
 }
 
 #[tokio::main]
@@ -38,56 +59,65 @@ async fn main() -> Result<()> {
 
     dotenvy::dotenv()?;
 
+    if let Ok(path) = std::env::var("CHARACTERS_PATH") {
+        info!("Reading characters from {path}");
+        character_registry::install(CharacterRegistry::load(path).await?)?;
+    }
+
+    if std::env::args().nth(1).as_deref() == Some("lint") {
+        return lint_from_cli().await;
+    }
+
     let token = std::env::var("DISCORD_TOKEN").expect("missing DISCORD_TOKEN");
+    let store_config = MoveStoreConfig {
+        snapshot_path: Some(
+            std::env::var("MOVE_SNAPSHOT_PATH")
+                .unwrap_or_else(|_| DEFAULT_MOVE_SNAPSHOT_PATH.into())
+                .into(),
+        ),
+        history: std::env::var("MOVE_HISTORY_DIR").ok().map(|directory| {
+            let defaults = MoveHistoryConfig::new(directory);
+            MoveHistoryConfig {
+                label: std::env::var("MOVE_HISTORY_LABEL").ok(),
+                max_versions: match std::env::var("MOVE_HISTORY_MAX_VERSIONS") {
+                    Ok(versions) => versions
+                        .parse()
+                        .expect("MOVE_HISTORY_MAX_VERSIONS must be a whole number"),
+                    Err(_) => defaults.max_versions,
+                },
+                max_age: std::env::var("MOVE_HISTORY_MAX_AGE_DAYS").ok().map(|days| {
+                    chrono::Duration::days(
+                        days.parse()
+                            .expect("MOVE_HISTORY_MAX_AGE_DAYS must be a whole number of days"),
+                    )
+                }),
+                ..defaults
+            }
+        }),
+        ..Default::default()
+    };
+    let move_repository = move_repository_from_env();
+    check_character_registry(&move_repository).await;
+    let refresh_interval = match std::env::var("MOVE_REFRESH_INTERVAL_MINUTES") {
+        Ok(minutes) => minutes
+            .parse::<u64>()
+            .expect("MOVE_REFRESH_INTERVAL_MINUTES must be a whole number of minutes"),
+        Err(_) => DEFAULT_MOVE_REFRESH_INTERVAL_MINUTES,
+    };
+    let subscriptions = Arc::new(
+        SubscriptionStore::load(
+            std::env::var("SUBSCRIPTIONS_PATH")
+                .unwrap_or_else(|_| DEFAULT_SUBSCRIPTIONS_PATH.into()),
+        )
+        .await?,
+    );
     let intents = serenity::GatewayIntents::GUILD_MESSAGES
         | serenity::GatewayIntents::DIRECT_MESSAGES
         | serenity::GatewayIntents::MESSAGE_CONTENT;
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![
-                ping(),
-                alisa(),
-                anna(),
-                armorking(),
-                asuka(),
-                azucena(),
-                bryan(),
-                claudio(),
-                clive(),
-                deviljin(),
-                dragunov(),
-                eddy(),
-                fahkumram(),
-                feng(),
-                heihachi(),
-                hwoarang(),
-                jack8(),
-                jin(),
-                jun(),
-                kazuya(),
-                king(),
-                kuma(),
-                lars(),
-                law(),
-                lee(),
-                leo(),
-                leroy(),
-                lidia(),
-                lili(),
-                nina(),
-                miaryzo(),
-                panda(),
-                paul(),
-                raven(),
-                reina(),
-                shaheen(),
-                steve(),
-                victor(),
-                xiaoyu(),
-                yoshimitsu(),
-                zafina(),
-            ],
+            commands: commands::commands(),
             prefix_options: poise::PrefixFrameworkOptions {
                 prefix: Some("+".into()),
                 mention_as_prefix: true,
@@ -96,7 +126,7 @@ async fn main() -> Result<()> {
             on_error: |error| Box::pin(on_error(error)),
             ..Default::default()
         })
-        .setup(|ctx, _ready, framework| {
+        .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 info!("Starting bot setup");
 
@@ -105,10 +135,32 @@ async fn main() -> Result<()> {
 
                 info!("Initializing frame service");
                 let frame_service =
-                    FrameService::try_new(WavuMoveRepository, JaroMoveMatcher).await?;
+                    FrameService::try_new(move_repository, JaroMoveMatcher, store_config).await?;
+                if let Some(fetched_at) = frame_service.stale_data_since() {
+                    warn!(
+                        "Could not reach the move repository, serving data from {}",
+                        fetched_at.to_rfc3339()
+                    );
+                }
+
+                if refresh_interval > 0 {
+                    info!("Refreshing move data every {refresh_interval} minutes");
+                    let announcements = AnnouncementService::new(
+                        subscriptions.clone(),
+                        DiscordAnnouncementSink::new(ctx.http.clone()),
+                    );
+                    spawn_periodic_refresh(
+                        frame_service.store(),
+                        Duration::from_secs(refresh_interval * 60),
+                        announcements,
+                    );
+                }
 
                 info!("Done setting up bot");
-                Ok(BotState { frame_service })
+                Ok(BotState {
+                    frame_service,
+                    subscriptions,
+                })
             })
         })
         .build();
@@ -122,6 +174,68 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn move_repository_from_env() -> Box<dyn MoveRepository> {
+    let mut move_repository: Box<dyn MoveRepository> = match std::env::var("MOVE_DATA_DIR") {
+        Ok(directory) => {
+            info!("Reading movelists from {directory}");
+            Box::new(FileMoveRepository::new(directory))
+        }
+        Err(_) => {
+            let defaults = WavuClientConfig::default();
+            let client = WavuClient::new(WavuClientConfig {
+                user_agent: std::env::var("WAVU_USER_AGENT").unwrap_or(defaults.user_agent),
+                max_concurrent_requests: match std::env::var("WAVU_MAX_CONCURRENT_REQUESTS") {
+                    Ok(requests) => requests
+                        .parse()
+                        .expect("WAVU_MAX_CONCURRENT_REQUESTS must be a whole number"),
+                    Err(_) => defaults.max_concurrent_requests,
+                },
+                ..defaults
+            });
+            let api_url = std::env::var("WAVU_API_URL").unwrap_or_else(|_| WAVU_API_URL.into());
+            Box::new(WavuMoveRepository::new(api_url, client))
+        }
+    };
+    if let Ok(directory) = std::env::var("MOVE_OVERRIDES_DIR") {
+        info!("Applying move overrides from {directory}");
+        move_repository = Box::new(OverridingMoveRepository::new(
+            move_repository,
+            FileOverrideSource::new(directory),
+        ));
+    }
+
+    move_repository
+}
+
+/// Warns about characters the move repository has moves for that the character
+/// registry doesn't know, e.g. a new DLC character
+async fn check_character_registry(repository: &dyn MoveRepository) {
+    match repository.character_names().await {
+        Some(Ok(names)) => {
+            for name in registry().unknown_names(&names) {
+                warn!(
+                    "{name} has moves but isn't in the character registry, their moves are ignored"
+                );
+            }
+        }
+        Some(Err(e)) => warn!("Failed to check the character registry: {:?}", e),
+        None => {}
+    }
+}
+
+/// `framebot lint` prints the problems found in the frame data, failing if there are any
+async fn lint_from_cli() -> Result<()> {
+    let store = MoveStore::try_new(move_repository_from_env(), MoveStoreConfig::default()).await?;
+    let report = store.lint();
+    println!("{report}");
+
+    if !report.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
 async fn on_error(error: poise::FrameworkError<'_, BotState, Error>) {
     match error {
         poise::FrameworkError::Setup { error, .. } => {
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use strsim::jaro;
 
 use crate::{
-    matchers::{CharacterMoveMatch, MoveMatcher},
+    matchers::{CharacterMoveMatch, MoveKeys, MoveMatcher, MoveQuery},
     tekken::{character::Character, character_move::CharacterMove},
 };
 
@@ -17,7 +17,7 @@ impl MoveMatcher for JaroMoveMatcher {
         query: &str,
         moves: &[CharacterMove],
     ) -> Option<CharacterMoveMatch> {
-        let full_query = format!("{character}-{query}").to_lowercase();
+        let full_query = format!("{}{query}", character.move_id_prefix()).to_lowercase();
 
         let matched_move = moves
             .iter()
@@ -96,6 +96,17 @@ impl MoveMatcher for JaroMoveMatcher {
             score: matched_move.0,
         })
     }
+
+    fn score(&self, query: &MoveQuery, keys: &MoveKeys) -> f64 {
+        let id = jaro(&keys.id, &query.id);
+
+        keys.name
+            .iter()
+            .chain(&keys.alts)
+            .chain(&keys.aliases)
+            .map(|key| jaro(key, &query.text))
+            .fold(id, f64::max)
+    }
 }
 
 #[cfg(test)]
@@ -195,6 +206,34 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("cs.2")]
+    #[case("Phoenix Smasher")]
+    #[case("QCF+2")]
+    #[case("deathfist")]
+    fn test_score_uses_closest_key(#[case] query: &str) {
+        let keys = MoveKeys::new(&sample_moves()[5]);
+
+        let score = JaroMoveMatcher.score(&MoveQuery::new(Character::Paul, query), &keys);
+
+        assert_eq!(score, 1f64);
+    }
+
+    #[test]
+    fn test_score_ranks_like_matching() {
+        let query = MoveQuery::new(Character::Paul, "qcf");
+        let moves = sample_moves();
+        let best = moves
+            .iter()
+            .max_by(|a, b| {
+                let score = |m| JaroMoveMatcher.score(&query, &MoveKeys::new(m));
+                score(a).total_cmp(&score(b))
+            })
+            .unwrap();
+
+        assert_eq!(best.id, "Paul-qcf");
+    }
+
     fn sample_moves() -> Vec<CharacterMove> {
         vec![
             CharacterMove {
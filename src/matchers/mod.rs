@@ -33,6 +33,55 @@ pub trait MoveMatcher: Send + Sync {
         query: &str,
         moves: &[CharacterMove],
     ) -> Option<CharacterMoveMatch>;
+
+    /// How well a move matches the query by its id, name, alts or aliases, whichever is
+    /// closest. For ranking many moves rather than picking one
+    fn score(&self, query: &MoveQuery, keys: &MoveKeys) -> f64;
+}
+
+/// What a move can be found by, normalized once up front so ranking moves doesn't redo
+/// it for every query
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveKeys {
+    pub id: String,
+    pub name: Option<String>,
+    pub alts: Vec<String>,
+    pub aliases: Vec<String>,
+}
+
+/// A query normalized like `MoveKeys`, prepared once to score many moves with
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveQuery {
+    /// The query as a move id of the character, e.g. "kazuya-ewgf"
+    pub id: String,
+    pub text: String,
+}
+
+impl MoveKeys {
+    pub fn new(m: &CharacterMove) -> Self {
+        let lowercase = |values: &[String]| {
+            values
+                .iter()
+                .map(|value| value.to_ascii_lowercase())
+                .collect()
+        };
+
+        Self {
+            id: m.id.to_ascii_lowercase(),
+            name: m.name.as_ref().map(|name| name.to_ascii_lowercase()),
+            alts: lowercase(&m.alt),
+            aliases: lowercase(&m.alias),
+        }
+    }
+}
+
+impl MoveQuery {
+    pub fn new(character: Character, query: &str) -> Self {
+        Self {
+            id: format!("{}{query}", character.move_id_prefix()).to_lowercase(),
+            text: query.to_lowercase(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -0,0 +1,378 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    move_store::MoveMap,
+    tekken::{
+        character::Character,
+        character_move::{CharacterMove, MoveField},
+    },
+};
+
+/// What changed between two sets of move data, e.g. after a balance patch
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct MoveDiff {
+    /// Only characters with changes, ordered by character
+    pub characters: Vec<CharacterDiff>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CharacterDiff {
+    pub character: Character,
+    pub added: Vec<CharacterMove>,
+    pub removed: Vec<CharacterMove>,
+    pub changed: Vec<MoveChange>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveChange {
+    pub id: String,
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldChange {
+    pub field: MoveField,
+    /// Empty if the move had no value
+    pub before: String,
+    /// Empty if the move has no value anymore
+    pub after: String,
+}
+
+impl MoveDiff {
+    /// Characters missing from either side are skipped, not having data for a character
+    /// doesn't mean all its moves were removed
+    pub fn between(before: &MoveMap, after: &MoveMap) -> Self {
+        let mut characters = before
+            .iter()
+            .filter_map(|(character, before_moves)| {
+                let after_moves = after.get(character)?;
+                CharacterDiff::between(*character, before_moves, after_moves)
+            })
+            .collect::<Vec<_>>();
+        characters.sort_by_key(|diff| diff.character);
+
+        Self { characters }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    pub fn character(&self, character: Character) -> Option<&CharacterDiff> {
+        self.characters
+            .iter()
+            .find(|diff| diff.character == character)
+    }
+}
+
+impl CharacterDiff {
+    /// Moves are matched by id. Returns `None` if nothing changed
+    fn between(
+        character: Character,
+        before: &[CharacterMove],
+        after: &[CharacterMove],
+    ) -> Option<Self> {
+        let before_by_id = before
+            .iter()
+            .map(|m| (m.id.as_str(), m))
+            .collect::<HashMap<_, _>>();
+        let after_by_id = after
+            .iter()
+            .map(|m| (m.id.as_str(), m))
+            .collect::<HashMap<_, _>>();
+
+        let added = after
+            .iter()
+            .filter(|m| !before_by_id.contains_key(m.id.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let removed = before
+            .iter()
+            .filter(|m| !after_by_id.contains_key(m.id.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let changed = after
+            .iter()
+            .filter_map(|m| MoveChange::between(before_by_id.get(m.id.as_str())?, m))
+            .collect::<Vec<_>>();
+
+        if added.is_empty() && removed.is_empty() && changed.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            character,
+            added,
+            removed,
+            changed,
+        })
+    }
+}
+
+impl MoveChange {
+    /// Returns `None` if no field changed
+    pub fn between(before: &CharacterMove, after: &CharacterMove) -> Option<Self> {
+        let fields = MoveField::all()
+            .filter_map(|field| {
+                let before = before.field_value(field);
+                let after = after.field_value(field);
+                (before != after).then_some(FieldChange {
+                    field,
+                    before,
+                    after,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            id: after.id.clone(),
+            fields,
+        })
+    }
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = |value: &str| match value {
+            "" => "none".to_string(),
+            value => value.to_string(),
+        };
+
+        write!(
+            f,
+            "{} {} → {}",
+            self.field,
+            value(&self.before),
+            value(&self.after)
+        )
+    }
+}
+
+impl Display for MoveChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = self
+            .fields
+            .iter()
+            .map(FieldChange::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{}: {fields}", self.id)
+    }
+}
+
+/// One line per change, e.g. "~ Paul-qcf+2: On Block -15 → -13"
+impl Display for CharacterDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.character)?;
+        for m in &self.added {
+            write!(f, "\n  + {}", m.id)?;
+        }
+        for m in &self.removed {
+            write!(f, "\n  - {}", m.id)?;
+        }
+        for change in &self.changed {
+            write!(f, "\n  ~ {change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for MoveDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let characters = self
+            .characters
+            .iter()
+            .map(CharacterDiff::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(f, "{characters}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tekken::frames::FrameField;
+
+    fn phoenix_smasher(on_block: &str, damage: &str) -> CharacterMove {
+        CharacterMove {
+            id: "Paul-qcf+2".into(),
+            damage: Some(damage.into()),
+            on_block: Some(on_block.into()),
+            on_hit: Some("+31a(+21)".into()),
+            ..Default::default()
+        }
+    }
+
+    fn simple_move(id: &str) -> CharacterMove {
+        CharacterMove {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_field_level_changes() {
+        let before = MoveMap::from([(Character::Paul, vec![phoenix_smasher("-15", "30")])]);
+        let after = MoveMap::from([(Character::Paul, vec![phoenix_smasher("-13", "32")])]);
+
+        let diff = MoveDiff::between(&before, &after);
+
+        let paul = diff.character(Character::Paul).unwrap();
+        assert!(paul.added.is_empty());
+        assert!(paul.removed.is_empty());
+        assert_eq!(
+            paul.changed,
+            vec![MoveChange {
+                id: "Paul-qcf+2".into(),
+                fields: vec![
+                    FieldChange {
+                        field: MoveField::Damage,
+                        before: "30".into(),
+                        after: "32".into(),
+                    },
+                    FieldChange {
+                        field: MoveField::Frames(FrameField::OnBlock),
+                        before: "-15".into(),
+                        after: "-13".into(),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parent_and_alternative_input_changes() {
+        let before = MoveMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                parent: Some("Paul-1".into()),
+                ..simple_move("Paul-1,2")
+            }],
+        )]);
+        let after = MoveMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                parent: Some("Paul-b+1".into()),
+                alt: vec!["Paul-f+1,2".into()],
+                ..simple_move("Paul-1,2")
+            }],
+        )]);
+
+        let diff = MoveDiff::between(&before, &after);
+
+        assert_eq!(
+            diff.character(Character::Paul).unwrap().changed,
+            vec![MoveChange {
+                id: "Paul-1,2".into(),
+                fields: vec![
+                    FieldChange {
+                        field: MoveField::Parent,
+                        before: "Paul-1".into(),
+                        after: "Paul-b+1".into(),
+                    },
+                    FieldChange {
+                        field: MoveField::Alt,
+                        before: "".into(),
+                        after: "Paul-f+1,2".into(),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed_moves() {
+        let before = MoveMap::from([(
+            Character::Paul,
+            vec![simple_move("Paul-1"), simple_move("Paul-b+1")],
+        )]);
+        let after = MoveMap::from([(
+            Character::Paul,
+            vec![simple_move("Paul-1"), simple_move("Paul-d+1+2")],
+        )]);
+
+        let diff = MoveDiff::between(&before, &after);
+
+        let paul = diff.character(Character::Paul).unwrap();
+        assert_eq!(paul.added, vec![simple_move("Paul-d+1+2")]);
+        assert_eq!(paul.removed, vec![simple_move("Paul-b+1")]);
+        assert!(paul.changed.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_and_missing_characters_are_left_out() {
+        let before = MoveMap::from([
+            (Character::Paul, vec![phoenix_smasher("-15", "30")]),
+            (Character::Kazuya, vec![simple_move("Kazuya-1")]),
+        ]);
+        let after = MoveMap::from([(Character::Paul, vec![phoenix_smasher("-15", "30")])]);
+
+        let diff = MoveDiff::between(&before, &after);
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_missing_values_are_the_same() {
+        let before = MoveMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                reach: Some("".into()),
+                ..simple_move("Paul-1")
+            }],
+        )]);
+        let after = MoveMap::from([(Character::Paul, vec![simple_move("Paul-1")])]);
+
+        assert!(MoveDiff::between(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let before = MoveMap::from([
+            (
+                Character::Paul,
+                vec![phoenix_smasher("-15", "30"), simple_move("Paul-b+1")],
+            ),
+            (Character::Kazuya, vec![simple_move("Kazuya-1")]),
+        ]);
+        let after = MoveMap::from([
+            (Character::Paul, vec![phoenix_smasher("-13", "30")]),
+            (
+                Character::Kazuya,
+                vec![
+                    simple_move("Kazuya-1"),
+                    CharacterMove {
+                        startup_frames: Some("i12".into()),
+                        ..simple_move("Kazuya-df+1")
+                    },
+                ],
+            ),
+        ]);
+
+        let diff = MoveDiff::between(&before, &after);
+
+        assert_eq!(
+            diff.to_string(),
+            "Kazuya\n  + Kazuya-df+1\nPaul\n  - Paul-b+1\n  ~ Paul-qcf+2: On Block -15 → -13"
+        );
+    }
+
+    #[test]
+    fn test_display_missing_values() {
+        let change = FieldChange {
+            field: MoveField::Crush,
+            before: "".into(),
+            after: "pc8~".into(),
+        };
+
+        assert_eq!(change.to_string(), "Crush none → pc8~");
+    }
+}
@@ -0,0 +1,518 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{
+    move_store::{
+        MoveMap,
+        diff::{FieldChange, MoveChange, MoveDiff},
+        snapshot::MoveSnapshot,
+    },
+    storage::write_atomically,
+    tekken::{character::Character, character_move::CharacterMove},
+};
+
+const INDEX_FILE: &str = "versions.json";
+
+#[derive(Debug, Clone)]
+pub struct MoveHistoryConfig {
+    /// Directory the versions and their index are kept in
+    pub directory: PathBuf,
+    /// Label for the data fetched from now on, e.g. "Season 2 / 2.01". Without one,
+    /// versions are labelled with the date they were fetched and a new one is only kept
+    /// when the data changed
+    pub label: Option<String>,
+    /// Oldest versions are removed once there are more than this
+    pub max_versions: usize,
+    /// Versions older than this are removed, except for the latest one
+    pub max_age: Option<chrono::Duration>,
+}
+
+impl MoveHistoryConfig {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            label: None,
+            max_versions: 20,
+            max_age: None,
+        }
+    }
+}
+
+/// A past state of the move data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveVersion {
+    pub label: String,
+    /// When the data of this version was last fetched
+    pub fetched_at: DateTime<Utc>,
+    file: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryIndex {
+    /// Oldest first
+    versions: Vec<MoveVersion>,
+}
+
+/// How a move differs from the version before
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveRevision {
+    Added,
+    Changed(Vec<FieldChange>),
+    Removed,
+}
+
+/// Labelled copies of the move data kept on disk, one per game version. Only the index
+/// is kept in memory, the data of a version is read when it's asked for
+#[derive(Debug)]
+pub struct MoveHistory {
+    config: MoveHistoryConfig,
+    versions: Mutex<Vec<MoveVersion>>,
+}
+
+impl MoveHistory {
+    /// Starts without versions if the directory has no index yet
+    pub async fn load(config: MoveHistoryConfig) -> Result<Self> {
+        let path = config.directory.join(INDEX_FILE);
+        let index = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice::<HistoryIndex>(&bytes)
+                .with_context(|| format!("failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HistoryIndex::default(),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+
+        Ok(Self {
+            config,
+            versions: Mutex::new(index.versions),
+        })
+    }
+
+    /// Oldest first
+    pub async fn versions(&self) -> Vec<MoveVersion> {
+        self.versions.lock().await.clone()
+    }
+
+    /// Finds a version by its whole label or a part of it, so "2.01" finds
+    /// "Season 2 / 2.01". Ignores case and prefers newer versions
+    pub async fn find(&self, query: &str) -> Option<MoveVersion> {
+        let query = query.trim().to_lowercase();
+        let versions = self.versions.lock().await;
+
+        let exact = versions
+            .iter()
+            .rev()
+            .find(|version| version.label.to_lowercase() == query);
+        let part = || {
+            versions.iter().rev().find(|version| {
+                version
+                    .label
+                    .to_lowercase()
+                    .split(|c: char| c == '/' || c.is_whitespace())
+                    .any(|part| part == query)
+            })
+        };
+
+        exact.or_else(part).cloned()
+    }
+
+    pub async fn moves(&self, version: &MoveVersion) -> Result<MoveMap> {
+        let snapshot = MoveSnapshot::read(&self.config.directory.join(&version.file)).await?;
+        Ok(snapshot.moves.into_owned())
+    }
+
+    /// Every version in which the move was added, changed or removed, oldest first.
+    /// Versions without data for the character are skipped
+    pub async fn move_revisions(
+        &self,
+        character: Character,
+        id: &str,
+    ) -> Result<Vec<(MoveVersion, MoveRevision)>> {
+        let mut revisions = Vec::new();
+        let mut previous: Option<CharacterMove> = None;
+
+        for version in self.versions().await {
+            let moves = self.moves(&version).await?;
+            let Some(character_moves) = moves.get(&character) else {
+                continue;
+            };
+            let current = character_moves.iter().find(|m| m.id == id).cloned();
+
+            let revision = match (&previous, &current) {
+                (None, Some(_)) => Some(MoveRevision::Added),
+                (Some(_), None) => Some(MoveRevision::Removed),
+                (Some(before), Some(after)) => MoveChange::between(before, after)
+                    .map(|change| MoveRevision::Changed(change.fields)),
+                (None, None) => None,
+            };
+            if let Some(revision) = revision {
+                revisions.push((version, revision));
+            }
+            previous = current;
+        }
+
+        Ok(revisions)
+    }
+
+    /// Saves `moves` as the version for the configured label, replacing what was saved
+    /// for it before. Old versions are removed according to the retention settings
+    pub async fn record(&self, fetched_at: DateTime<Utc>, moves: &MoveMap) -> Result<()> {
+        let mut versions = self.versions.lock().await;
+        let label = self
+            .config
+            .label
+            .clone()
+            .unwrap_or_else(|| fetched_at.format("%Y-%m-%d").to_string());
+
+        // A label is only ever kept once, even when it isn't the latest version, e.g.
+        // after going back to an older configured label
+        let existing = versions.iter().find(|v| v.label == label).cloned();
+        let latest = versions.last().cloned();
+        let version = match (existing, latest) {
+            (Some(existing), _) => MoveVersion {
+                fetched_at,
+                ..existing
+            },
+            (None, Some(latest)) if self.config.label.is_none() => {
+                let latest_moves = self.moves(&latest).await?;
+                if MoveDiff::between(&latest_moves, moves).is_empty() {
+                    return Ok(());
+                }
+                new_version(label, fetched_at, &versions)
+            }
+            _ => new_version(label, fetched_at, &versions),
+        };
+
+        MoveSnapshot::new(fetched_at, moves)
+            .write(&self.config.directory.join(&version.file))
+            .await?;
+        info!("Recorded move data version {}", version.label);
+        versions.retain(|v| v.file != version.file);
+        versions.push(version);
+
+        let (kept, expired) = self.apply_retention(std::mem::take(&mut *versions), Utc::now());
+        *versions = kept;
+        for version in expired {
+            let path = self.config.directory.join(&version.file);
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => info!("Removed move data version {}", version.label),
+                Err(e) => warn!("Failed to remove old version {}: {:?}", path.display(), e),
+            }
+        }
+
+        let index = HistoryIndex {
+            versions: versions.clone(),
+        };
+        write_atomically(
+            &self.config.directory.join(INDEX_FILE),
+            &serde_json::to_vec_pretty(&index)?,
+        )
+        .await
+    }
+
+    /// Splits the versions into the ones to keep and the ones that are too old or too
+    /// many. The latest version is always kept
+    fn apply_retention(
+        &self,
+        mut versions: Vec<MoveVersion>,
+        now: DateTime<Utc>,
+    ) -> (Vec<MoveVersion>, Vec<MoveVersion>) {
+        let Some(latest) = versions.pop() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let too_many = (versions.len() + 1).saturating_sub(self.config.max_versions.max(1));
+        let mut expired = versions
+            .drain(..too_many.min(versions.len()))
+            .collect::<Vec<_>>();
+        if let Some(max_age) = self.config.max_age {
+            let (old, recent) = versions
+                .into_iter()
+                .partition(|version| now - version.fetched_at > max_age);
+            versions = recent;
+            expired.extend::<Vec<_>>(old);
+        }
+        versions.push(latest);
+
+        (versions, expired)
+    }
+}
+
+/// Files are named after when the data was fetched, with a number added in the unlikely
+/// case that another version was fetched at the same time
+fn new_version(label: String, fetched_at: DateTime<Utc>, existing: &[MoveVersion]) -> MoveVersion {
+    let name = fetched_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+    let mut file = format!("{name}.json");
+    let mut n = 1;
+    while existing.iter().any(|version| version.file == file) {
+        file = format!("{name}-{n}.json");
+        n += 1;
+    }
+
+    MoveVersion {
+        label,
+        fetched_at,
+        file,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn config(directory: &std::path::Path, label: Option<&str>) -> MoveHistoryConfig {
+        MoveHistoryConfig {
+            label: label.map(String::from),
+            ..MoveHistoryConfig::new(directory)
+        }
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 10, day, 12, 0, 0).unwrap()
+    }
+
+    fn phoenix_smasher(on_block: &str) -> MoveMap {
+        MoveMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                id: "Paul-qcf+2".into(),
+                on_block: Some(on_block.into()),
+                ..Default::default()
+            }],
+        )])
+    }
+
+    fn labels(versions: &[MoveVersion]) -> Vec<&str> {
+        versions.iter().map(|v| v.label.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_keeps_a_version_per_label() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = MoveHistory::load(config(dir.path(), Some("2.00")))
+            .await
+            .unwrap();
+        history
+            .record(day(1), &phoenix_smasher("-15"))
+            .await
+            .unwrap();
+        history
+            .record(day(2), &phoenix_smasher("-14"))
+            .await
+            .unwrap();
+
+        let history = MoveHistory::load(config(dir.path(), Some("Season 2 / 2.01")))
+            .await
+            .unwrap();
+        history
+            .record(day(3), &phoenix_smasher("-13"))
+            .await
+            .unwrap();
+
+        let versions = history.versions().await;
+        assert_eq!(labels(&versions), vec!["2.00", "Season 2 / 2.01"]);
+        assert_eq!(versions[0].fetched_at, day(2));
+        assert_eq!(
+            history.moves(&versions[0]).await.unwrap(),
+            phoenix_smasher("-14")
+        );
+        assert_eq!(
+            history.moves(&versions[1]).await.unwrap(),
+            phoenix_smasher("-13")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replaces_older_version_with_the_same_label() {
+        let dir = tempfile::tempdir().unwrap();
+        for (label, fetched_at, on_block) in [
+            ("2.00", day(1), "-15"),
+            ("2.01", day(2), "-14"),
+            ("2.00", day(3), "-13"),
+        ] {
+            let history = MoveHistory::load(config(dir.path(), Some(label)))
+                .await
+                .unwrap();
+            history
+                .record(fetched_at, &phoenix_smasher(on_block))
+                .await
+                .unwrap();
+        }
+
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+        let versions = history.versions().await;
+        assert_eq!(labels(&versions), vec!["2.01", "2.00"]);
+        let found = history.find("2.00").await.unwrap();
+        assert_eq!(found.fetched_at, day(3));
+        assert_eq!(history.moves(&found).await.unwrap(), phoenix_smasher("-13"));
+    }
+
+    #[tokio::test]
+    async fn test_dated_versions_only_when_data_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+
+        history
+            .record(day(1), &phoenix_smasher("-15"))
+            .await
+            .unwrap();
+        history
+            .record(day(2), &phoenix_smasher("-15"))
+            .await
+            .unwrap();
+        history
+            .record(day(3), &phoenix_smasher("-13"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            labels(&history.versions().await),
+            vec!["2025-10-01", "2025-10-03"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find() {
+        let dir = tempfile::tempdir().unwrap();
+        for (i, label) in ["Season 1 / 1.08", "Season 2 / 2.00", "Season 2 / 2.01"]
+            .iter()
+            .enumerate()
+        {
+            MoveHistory::load(config(dir.path(), Some(label)))
+                .await
+                .unwrap()
+                .record(day(i as u32 + 1), &phoenix_smasher("-15"))
+                .await
+                .unwrap();
+        }
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+
+        let find = async |query| history.find(query).await.map(|v| v.label);
+
+        assert_eq!(find("2.00").await.as_deref(), Some("Season 2 / 2.00"));
+        assert_eq!(
+            find("season 1 / 1.08").await.as_deref(),
+            Some("Season 1 / 1.08")
+        );
+        assert_eq!(find("2").await.as_deref(), Some("Season 2 / 2.01"));
+        assert_eq!(find("3.00").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_move_revisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let versions = [
+            ("1.08", MoveMap::from([(Character::Paul, vec![])])),
+            ("2.00", phoenix_smasher("-15")),
+            ("2.01", phoenix_smasher("-15")),
+            ("2.02", phoenix_smasher("-13")),
+            ("2.03", MoveMap::new()),
+            ("2.04", MoveMap::from([(Character::Paul, vec![])])),
+        ];
+        for (i, (label, moves)) in versions.iter().enumerate() {
+            MoveHistory::load(config(dir.path(), Some(label)))
+                .await
+                .unwrap()
+                .record(day(i as u32 + 1), moves)
+                .await
+                .unwrap();
+        }
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+
+        let revisions = history
+            .move_revisions(Character::Paul, "Paul-qcf+2")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|(version, revision)| (version.label.as_str(), revision.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2.00", MoveRevision::Added),
+                (
+                    "2.02",
+                    MoveRevision::Changed(vec![FieldChange {
+                        field: crate::tekken::character_move::MoveField::Frames(
+                            crate::tekken::frames::FrameField::OnBlock
+                        ),
+                        before: "-15".into(),
+                        after: "-13".into(),
+                    }])
+                ),
+                ("2.04", MoveRevision::Removed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_removes_versions_beyond_max_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        for (i, label) in ["2.00", "2.01", "2.02"].iter().enumerate() {
+            MoveHistory::load(MoveHistoryConfig {
+                max_versions: 2,
+                ..config(dir.path(), Some(label))
+            })
+            .await
+            .unwrap()
+            .record(day(i as u32 + 1), &phoenix_smasher("-15"))
+            .await
+            .unwrap();
+        }
+
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+        let versions = history.versions().await;
+
+        assert_eq!(labels(&versions), vec!["2.01", "2.02"]);
+        let files = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(files, 3);
+    }
+
+    #[tokio::test]
+    async fn test_versions_fetched_at_the_same_time() {
+        let dir = tempfile::tempdir().unwrap();
+        for label in ["2.00", "2.01"] {
+            MoveHistory::load(config(dir.path(), Some(label)))
+                .await
+                .unwrap()
+                .record(day(1), &phoenix_smasher("-15"))
+                .await
+                .unwrap();
+        }
+
+        let history = MoveHistory::load(config(dir.path(), None)).await.unwrap();
+
+        assert_eq!(labels(&history.versions().await), vec!["2.00", "2.01"]);
+    }
+
+    #[test]
+    fn test_removes_old_versions_but_keeps_the_latest() {
+        let history = MoveHistory {
+            config: MoveHistoryConfig {
+                max_age: Some(chrono::Duration::days(7)),
+                ..MoveHistoryConfig::new("history")
+            },
+            versions: Mutex::new(Vec::new()),
+        };
+        let versions = vec![
+            new_version("2.00".into(), day(1), &[]),
+            new_version("2.01".into(), day(2), &[]),
+        ];
+
+        let (kept, expired) = history.apply_retention(versions.clone(), day(20));
+
+        assert_eq!(labels(&kept), vec!["2.01"]);
+        assert_eq!(labels(&expired), vec!["2.00"]);
+
+        let (kept, expired) = history.apply_retention(versions, day(5));
+
+        assert_eq!(labels(&kept), vec!["2.00", "2.01"]);
+        assert!(expired.is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::{
+    matchers::MoveKeys,
+    move_store::MoveMap,
+    tekken::{character::Character, character_move::CharacterMove},
+};
+
+/// Longest label and value Discord accepts for an autocomplete choice
+const MAX_CHOICE_LENGTH: usize = 100;
+
+/// What every character's moves are found by and what autocomplete shows for them,
+/// built once per data set so suggestions don't have to copy movelists, normalize or
+/// format moves while the user is typing
+#[derive(Debug, Default)]
+pub struct MoveIndex {
+    characters: HashMap<Character, Vec<IndexedMove>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IndexedMove {
+    pub keys: MoveKeys,
+    pub suggestion: MoveSuggestion,
+}
+
+/// An autocomplete choice for a move
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveSuggestion {
+    /// e.g. "f,n,d,df#2 — Electric Wind God Fist (i11, +5)"
+    pub label: String,
+    /// Id without the character's prefix, which finds the move when used as the query
+    pub value: String,
+}
+
+impl MoveIndex {
+    pub fn new(moves: &MoveMap) -> Self {
+        let characters = moves
+            .iter()
+            .map(|(character, moves)| {
+                let indexed = moves
+                    .iter()
+                    .map(|m| IndexedMove {
+                        keys: MoveKeys::new(m),
+                        suggestion: MoveSuggestion::new(*character, m),
+                    })
+                    .collect();
+                (*character, indexed)
+            })
+            .collect();
+
+        Self { characters }
+    }
+
+    /// Empty if there's no data for `character`
+    pub fn moves(&self, character: Character) -> &[IndexedMove] {
+        self.characters
+            .get(&character)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+impl MoveSuggestion {
+    pub fn new(character: Character, m: &CharacterMove) -> Self {
+        let id =
+            m.id.strip_prefix(&character.move_id_prefix())
+                .unwrap_or(&m.id);
+
+        let mut label = id.to_string();
+        if let Some(name) = &m.name {
+            label.push_str(&format!(" — {name}"));
+        }
+        let frames = [&m.startup_frames, &m.on_block]
+            .into_iter()
+            .flatten()
+            .filter(|frames| !frames.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !frames.is_empty() {
+            label.push_str(&format!(" ({})", frames.join(", ")));
+        }
+
+        Self {
+            label: truncate(&label),
+            value: truncate(id),
+        }
+    }
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= MAX_CHOICE_LENGTH {
+        return s.to_string();
+    }
+
+    let mut truncated = s.chars().take(MAX_CHOICE_LENGTH - 1).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        Some("Electric Wind God Fist"),
+        Some("i11~12"),
+        Some("+5"),
+        "f,n,d,df#2 — Electric Wind God Fist (i11~12, +5)"
+    )]
+    #[case(None, Some("i11~12"), None, "f,n,d,df#2 (i11~12)")]
+    #[case(
+        Some("Electric Wind God Fist"),
+        None,
+        Some(""),
+        "f,n,d,df#2 — Electric Wind God Fist"
+    )]
+    #[case(None, None, None, "f,n,d,df#2")]
+    fn test_suggestion_label(
+        #[case] name: Option<&str>,
+        #[case] startup: Option<&str>,
+        #[case] on_block: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let m = CharacterMove {
+            id: "Kazuya-f,n,d,df#2".into(),
+            name: name.map(String::from),
+            startup_frames: startup.map(String::from),
+            on_block: on_block.map(String::from),
+            ..Default::default()
+        };
+
+        let suggestion = MoveSuggestion::new(Character::Kazuya, &m);
+
+        assert_eq!(suggestion.label, expected);
+        assert_eq!(suggestion.value, "f,n,d,df#2");
+    }
+
+    #[test]
+    fn test_long_labels_are_truncated() {
+        let m = CharacterMove {
+            id: "Paul-1".into(),
+            name: Some("Very ".repeat(30)),
+            ..Default::default()
+        };
+
+        let suggestion = MoveSuggestion::new(Character::Paul, &m);
+
+        assert_eq!(suggestion.label.chars().count(), MAX_CHOICE_LENGTH);
+        assert!(suggestion.label.ends_with('…'));
+    }
+
+    #[test]
+    fn test_index_keeps_movelist_order() {
+        let moves = MoveMap::from([(
+            Character::Paul,
+            vec![
+                CharacterMove {
+                    id: "Paul-qcf+2".into(),
+                    ..Default::default()
+                },
+                CharacterMove {
+                    id: "Paul-1".into(),
+                    ..Default::default()
+                },
+            ],
+        )]);
+
+        let index = MoveIndex::new(&moves);
+
+        let values = index
+            .moves(Character::Paul)
+            .iter()
+            .map(|m| m.suggestion.value.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["qcf+2", "1"]);
+        assert!(index.moves(Character::Kazuya).is_empty());
+    }
+}
@@ -0,0 +1,331 @@
+use std::{fmt::Display, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{
+    move_store::MoveMap,
+    tekken::{
+        character::Character,
+        character_move::{CharacterMove, MoveField, movelist_problems},
+        frames::FrameField,
+    },
+};
+
+/// Fields a move is hard to use without, e.g. for punishers
+const CRITICAL_FIELDS: [MoveField; 3] = [
+    MoveField::Frames(FrameField::Startup),
+    MoveField::HitLevel,
+    MoveField::Damage,
+];
+
+/// Wiki links and templates, bold or italic quotes, HTML tags or entities and template
+/// placeholders that should have been cleaned up when the data was loaded
+static MARKUP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[\[|\]\]|\{\{|\}\}|''|</?[a-zA-Z][^>]*>|&[a-zA-Z]+;|&#\d+;|\$\{\w+\}").unwrap()
+});
+
+/// Problems found in the moves of every character, for finding bad data before users do
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct LintReport {
+    /// Only characters with problems, ordered by character
+    pub characters: Vec<CharacterLint>,
+    pub checked_moves: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CharacterLint {
+    pub character: Character,
+    pub problems: Vec<LintProblem>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintProblem {
+    /// Position of the move in the character's movelist, starting at 1
+    pub entry: usize,
+    /// Empty if the move has no id
+    pub id: String,
+    pub message: String,
+}
+
+impl LintReport {
+    pub fn new(moves: &MoveMap) -> Self {
+        let mut characters = moves
+            .iter()
+            .map(|(character, moves)| CharacterLint {
+                character: *character,
+                problems: lint_moves(*character, moves),
+            })
+            .filter(|lint| !lint.problems.is_empty())
+            .collect::<Vec<_>>();
+        characters.sort_by_key(|lint| lint.character);
+
+        Self {
+            characters,
+            checked_moves: moves.values().map(Vec::len).sum(),
+        }
+    }
+
+    /// Report on the moves of a single character
+    pub fn for_character(character: Character, moves: &[CharacterMove]) -> Self {
+        let problems = lint_moves(character, moves);
+        let characters = if problems.is_empty() {
+            Vec::new()
+        } else {
+            vec![CharacterLint {
+                character,
+                problems,
+            }]
+        };
+
+        Self {
+            characters,
+            checked_moves: moves.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+
+    pub fn problem_count(&self) -> usize {
+        self.characters.iter().map(|lint| lint.problems.len()).sum()
+    }
+}
+
+/// Everything that's wrong with a movelist
+pub fn lint_moves(character: Character, moves: &[CharacterMove]) -> Vec<LintProblem> {
+    let problem = |i: usize, message: String| LintProblem {
+        entry: i + 1,
+        id: moves[i].id.clone(),
+        message,
+    };
+    let mut problems = movelist_problems(character, moves)
+        .into_iter()
+        .map(|(i, message)| problem(i, message))
+        .collect::<Vec<_>>();
+
+    for (i, m) in moves.iter().enumerate() {
+        if m.id.trim().is_empty() {
+            continue;
+        }
+
+        for field in CRITICAL_FIELDS {
+            if m.field_value(field).trim().is_empty() {
+                problems.push(problem(i, format!("{field} is empty")));
+            }
+        }
+        if let Some(markup) = MARKUP_REGEX.find(&m.id) {
+            problems.push(problem(
+                i,
+                format!("id contains markup \"{}\"", markup.as_str()),
+            ));
+        }
+        for field in MoveField::all() {
+            if let Some(markup) = MARKUP_REGEX.find(&m.field_value(field)) {
+                problems.push(problem(
+                    i,
+                    format!("{field} contains markup \"{}\"", markup.as_str()),
+                ));
+            }
+        }
+    }
+
+    // Keep every move's problems together, in movelist order
+    problems.sort_by_key(|problem| problem.entry);
+    problems
+}
+
+fn problem_count(count: usize) -> String {
+    match count {
+        1 => "1 problem".into(),
+        count => format!("{count} problems"),
+    }
+}
+
+impl Display for LintProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = if self.id.is_empty() {
+            "no id"
+        } else {
+            &self.id
+        };
+        write!(f, "entry {} ({id}): {}", self.entry, self.message)
+    }
+}
+
+/// The character followed by one problem per line
+impl Display for CharacterLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({})",
+            self.character,
+            problem_count(self.problems.len())
+        )?;
+        for problem in &self.problems {
+            write!(f, "\n  {problem}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for LintReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Checked {} moves, found {}",
+            self.checked_moves,
+            problem_count(self.problem_count())
+        )?;
+        for character in &self.characters {
+            write!(f, "\n\n{character}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn good_move(id: &str) -> CharacterMove {
+        CharacterMove {
+            id: id.into(),
+            target: Some("m".into()),
+            damage: Some("30".into()),
+            startup_frames: Some("i14~15".into()),
+            on_block: Some("-15".into()),
+            ..Default::default()
+        }
+    }
+
+    fn messages(character: Character, moves: &[CharacterMove]) -> Vec<String> {
+        lint_moves(character, moves)
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_good_moves_have_no_problems() {
+        let moves = vec![good_move("Paul-qcf+2"), good_move("Paul-1")];
+
+        assert!(lint_moves(Character::Paul, &moves).is_empty());
+    }
+
+    #[test]
+    fn test_empty_critical_fields() {
+        let m = CharacterMove {
+            startup_frames: None,
+            target: Some(" ".into()),
+            ..good_move("Paul-1")
+        };
+
+        assert_eq!(
+            messages(Character::Paul, &[m]),
+            vec!["Startup is empty", "Hit Level is empty"]
+        );
+    }
+
+    #[test]
+    fn test_ids() {
+        let moves = vec![
+            good_move("Paul-1"),
+            good_move("Paul-1"),
+            good_move("Kazuya-1"),
+            good_move(""),
+        ];
+
+        assert_eq!(
+            messages(Character::Paul, &moves),
+            vec![
+                "id is used more than once",
+                "id doesn't start with \"Paul-\"",
+                "id is empty",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unparsable_values() {
+        let m = CharacterMove {
+            on_block: Some("+5x".into()),
+            target: Some("x".into()),
+            ..good_move("Paul-1")
+        };
+
+        let problems = messages(Character::Paul, &[m]);
+
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("On Block: "));
+        assert!(problems[1].starts_with("Hit Level: "));
+    }
+
+    #[rstest]
+    #[case("[[Eddy combos#Staples|+31a(+24)]]", "[[")]
+    #[case("{{Plus|+5}}", "{{")]
+    #[case("+5<br>", "<br>")]
+    #[case("+5 &nbsp;", "&nbsp;")]
+    #[case("'''+5'''", "''")]
+    #[case("+5${heat}", "${heat}")]
+    fn test_leftover_markup(#[case] on_hit: &str, #[case] markup: &str) {
+        let m = CharacterMove {
+            on_hit: Some(on_hit.into()),
+            ..good_move("Paul-1")
+        };
+
+        let problems = messages(Character::Paul, &[m]);
+
+        assert!(
+            problems.contains(&format!("On Hit contains markup \"{markup}\"")),
+            "{problems:?}"
+        );
+    }
+
+    #[test]
+    fn test_report_for_character_only_counts_their_moves() {
+        let moves = vec![CharacterMove {
+            id: "Paul-1".into(),
+            ..Default::default()
+        }];
+
+        let report = LintReport::for_character(Character::Paul, &moves);
+
+        assert_eq!(report.checked_moves, 1);
+        assert_eq!(report.characters.len(), 1);
+        assert_eq!(report.characters[0].character, Character::Paul);
+    }
+
+    #[test]
+    fn test_report_groups_by_character() {
+        let moves = MoveMap::from([
+            (
+                Character::Paul,
+                vec![good_move("Paul-1"), good_move("Paul-1")],
+            ),
+            (Character::Kazuya, vec![good_move("Kazuya-1")]),
+            (
+                Character::Bryan,
+                vec![CharacterMove {
+                    damage: None,
+                    ..good_move("Bryan-uf+4")
+                }],
+            ),
+        ]);
+
+        let report = LintReport::new(&moves);
+
+        assert_eq!(report.checked_moves, 4);
+        assert_eq!(report.problem_count(), 2);
+        assert_eq!(
+            report.to_string(),
+            "Checked 4 moves, found 2 problems\n\n\
+             Bryan (1 problem)\n  entry 1 (Bryan-uf+4): Damage is empty\n\n\
+             Paul (1 problem)\n  entry 2 (Paul-1): id is used more than once"
+        );
+    }
+}
@@ -0,0 +1,1090 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    move_store::{
+        diff::MoveDiff,
+        history::{MoveHistory, MoveHistoryConfig},
+        index::MoveIndex,
+        lint::LintReport,
+        snapshot::MoveSnapshot,
+    },
+    repositories::{MoveRepository, SourceUnavailable},
+    tekken::{character::Character, character_move::CharacterMove},
+};
+use anyhow::{Context, Result, bail};
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+pub mod diff;
+pub mod history;
+pub mod index;
+pub mod lint;
+pub mod refresh;
+pub mod snapshot;
+
+pub type MoveMap = HashMap<Character, Vec<CharacterMove>>;
+
+#[derive(Debug, Clone)]
+pub struct MoveStoreConfig {
+    /// File the store persists its data to after every successful fetch, and falls
+    /// back to when the repository can't be reached at startup
+    pub snapshot_path: Option<PathBuf>,
+    /// How many times fetching a character's moves is attempted before giving up.
+    /// Repositories that retry requests themselves are only asked once
+    pub fetch_attempts: usize,
+    /// Wait before the first retry, doubled after every failed attempt
+    pub retry_backoff: Duration,
+    /// Where labelled versions of the data are kept to look up past game versions
+    pub history: Option<MoveHistoryConfig>,
+}
+
+impl Default for MoveStoreConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_path: None,
+            fetch_attempts: 3,
+            retry_backoff: Duration::from_secs(1),
+            history: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LoadedMoves {
+    moves: MoveMap,
+    /// Characters we have no data for because fetching their moves failed
+    unavailable: HashSet<Character>,
+    /// Characters that changed but kept their previous data because fetching the new
+    /// data failed
+    outdated: HashSet<Character>,
+    fetched_at: DateTime<Utc>,
+    stale: bool,
+    /// Built along with the data so autocomplete never has to wait for it
+    index: Arc<MoveIndex>,
+}
+
+/// Holds the move data for every character. Readers always see a complete data set,
+/// refreshes build the new data on the side and swap it in once it's done
+#[derive(Debug)]
+pub struct MoveStore<M: MoveRepository> {
+    move_repository: M,
+    config: MoveStoreConfig,
+    loaded: ArcSwap<LoadedMoves>,
+    refresh_lock: Mutex<()>,
+    history: Option<MoveHistory>,
+}
+
+impl<M: MoveRepository> MoveStore<M> {
+    pub async fn try_new(move_repository: M, config: MoveStoreConfig) -> Result<Self> {
+        let history = match &config.history {
+            Some(history_config) => Some(MoveHistory::load(history_config.clone()).await?),
+            None => None,
+        };
+
+        // Taken before fetching so edits made while loading show up in the next refresh
+        let started_at = Utc::now();
+        let fetch_error = match Self::create_new_move_map(&move_repository, &config).await {
+            Ok((moves, unavailable)) => {
+                let store = Self::new(
+                    move_repository,
+                    config,
+                    history,
+                    LoadedMoves {
+                        index: Arc::new(MoveIndex::new(&moves)),
+                        moves,
+                        unavailable,
+                        outdated: HashSet::new(),
+                        fetched_at: started_at,
+                        stale: false,
+                    },
+                );
+                store.write_snapshot().await;
+                return Ok(store);
+            }
+            Err(e) => e,
+        };
+
+        let Some(snapshot_path) = &config.snapshot_path else {
+            return Err(fetch_error);
+        };
+
+        warn!(
+            "Failed to fetch moves, falling back to snapshot {}: {:?}",
+            snapshot_path.display(),
+            fetch_error
+        );
+
+        let snapshot = MoveSnapshot::read(snapshot_path)
+            .await
+            .with_context(|| format!("failed to fetch moves: {fetch_error:#}"))?;
+
+        warn!(
+            "Running on stale data fetched at {}",
+            snapshot.fetched_at.to_rfc3339()
+        );
+
+        let moves = snapshot.moves.into_owned();
+        let loaded = LoadedMoves {
+            unavailable: Self::characters_missing_from(&moves),
+            outdated: HashSet::new(),
+            index: Arc::new(MoveIndex::new(&moves)),
+            moves,
+            fetched_at: snapshot.fetched_at,
+            stale: true,
+        };
+        Ok(Self::new(move_repository, config, history, loaded))
+    }
+
+    fn new(
+        move_repository: M,
+        config: MoveStoreConfig,
+        history: Option<MoveHistory>,
+        loaded: LoadedMoves,
+    ) -> Self {
+        Self {
+            move_repository,
+            config,
+            loaded: ArcSwap::from_pointee(loaded),
+            refresh_lock: Mutex::new(()),
+            history,
+        }
+    }
+
+    /// Fetches moves again and swaps them in. If the repository can tell which characters
+    /// changed since the last fetch only those are fetched, otherwise everything is.
+    /// Stale data from a snapshot is always fetched again in full, the repository may not
+    /// remember changes that far back. Characters that fail to fetch keep their current
+    /// data and are fetched again on the next refresh, and if every character fails
+    /// nothing changes. Concurrent refreshes are serialized, lookups are never
+    /// blocked. Returns what changed compared to the data the store had before
+    pub async fn refresh_moves(&self) -> Result<MoveDiff> {
+        let _guard = self.refresh_lock.lock().await;
+
+        let started_at = Utc::now();
+        let current = self.loaded.load_full();
+
+        let changed = if current.stale {
+            None
+        } else {
+            self.move_repository
+                .changed_characters(current.fetched_at)
+                .await
+        };
+
+        let (mut move_map, failed) = match changed {
+            Some(Ok(changed)) => {
+                info!(
+                    "{} characters changed since {}",
+                    changed.len(),
+                    current.fetched_at.to_rfc3339()
+                );
+                // Characters we have no data for or whose changes couldn't be fetched
+                // last time are worth another try as well
+                let characters = changed
+                    .iter()
+                    .chain(&current.unavailable)
+                    .chain(&current.outdated)
+                    .copied()
+                    .collect::<HashSet<_>>()
+                    .into_iter();
+                let (fetched, failed) =
+                    Self::fetch_characters(&self.move_repository, &self.config, characters).await;
+
+                let mut move_map = current.moves.clone();
+                move_map.extend(fetched);
+                (move_map, failed)
+            }
+            Some(Err(e)) => {
+                warn!("Couldn't tell which characters changed, refreshing all: {e:?}");
+                Self::create_new_move_map(&self.move_repository, &self.config).await?
+            }
+            None => Self::create_new_move_map(&self.move_repository, &self.config).await?,
+        };
+
+        let mut outdated = HashSet::new();
+        for character in failed {
+            if let Some(moves) = current.moves.get(&character) {
+                warn!("Keeping previous moves for {character}");
+                move_map.insert(character, moves.clone());
+                outdated.insert(character);
+            }
+        }
+
+        let diff = MoveDiff::between(&current.moves, &move_map);
+        self.loaded.store(Arc::new(LoadedMoves {
+            unavailable: Self::characters_missing_from(&move_map),
+            outdated,
+            index: Arc::new(MoveIndex::new(&move_map)),
+            moves: move_map,
+            fetched_at: started_at,
+            stale: false,
+        }));
+        self.write_snapshot().await;
+        Ok(diff)
+    }
+
+    /// Returns the moves of every character that could be fetched together with the
+    /// characters that couldn't. Only fails when no character could be fetched at all
+    async fn create_new_move_map(
+        move_repository: &M,
+        config: &MoveStoreConfig,
+    ) -> Result<(MoveMap, HashSet<Character>)> {
+        // One bulk load is far easier on the wiki than a request per character, the
+        // per character fetches are only a fallback for when it fails
+        let bulk = with_retry(move_repository, config, "all moves", || async {
+            move_repository.all_moves().await.transpose()
+        })
+        .await;
+        match bulk {
+            // Characters without any rows may have been cut off, they count as failed
+            // rather than as having no moves
+            Ok(Some(move_map)) => {
+                let missing = Self::characters_missing_from(&move_map);
+                return Ok((move_map, missing));
+            }
+            Ok(None) => {}
+            // A request per character would only hit a struggling source harder
+            Err(e) if e.chain().any(|cause| cause.is::<SourceUnavailable>()) => {
+                return Err(e.context("failed to fetch all moves"));
+            }
+            Err(e) => warn!(
+                "Failed to fetch all moves at once, fetching every character separately: {:?}",
+                e
+            ),
+        }
+
+        let (move_map, failed) =
+            Self::fetch_characters(move_repository, config, Character::iter()).await;
+
+        if move_map.is_empty() {
+            bail!("failed to fetch moves for every character");
+        }
+
+        Ok((move_map, failed))
+    }
+
+    /// Fetches the characters one by one, all at the same time
+    async fn fetch_characters(
+        move_repository: &M,
+        config: &MoveStoreConfig,
+        characters: impl Iterator<Item = Character>,
+    ) -> (MoveMap, HashSet<Character>) {
+        let futures: Vec<_> = characters
+            .map(|character| async move {
+                let result = with_retry(
+                    move_repository,
+                    config,
+                    &format!("moves for {character}"),
+                    || move_repository.character_moves(character),
+                )
+                .await;
+                (character, result)
+            })
+            .collect();
+
+        let mut move_map = HashMap::new();
+        let mut failed = HashSet::new();
+        let results = join_all(futures).await;
+
+        for (character, result) in results {
+            match result {
+                Ok(move_data) => {
+                    move_map.insert(character, move_data);
+                }
+                Err(e) => {
+                    error!("Failed to fetch moves for {character}: {:?}", e);
+                    failed.insert(character);
+                }
+            }
+        }
+
+        (move_map, failed)
+    }
+
+    fn characters_missing_from(moves: &MoveMap) -> HashSet<Character> {
+        Character::iter()
+            .filter(|character| !moves.contains_key(character))
+            .collect()
+    }
+
+    /// Writes the snapshot and records the data in the history. Failing to do either
+    /// shouldn't take down the bot, the data in memory is still fine, so errors are only
+    /// logged
+    async fn write_snapshot(&self) {
+        let loaded = self.loaded.load();
+
+        // A snapshot with holes in it is worse than an older complete one
+        if !loaded.unavailable.is_empty() {
+            warn!("Not writing move snapshot, some characters are missing data");
+            return;
+        }
+
+        if let Some(snapshot_path) = &self.config.snapshot_path {
+            let snapshot = MoveSnapshot::new(loaded.fetched_at, &loaded.moves);
+            match snapshot.write(snapshot_path).await {
+                Ok(()) => info!("Wrote move snapshot to {}", snapshot_path.display()),
+                Err(e) => error!("Failed to write move snapshot: {:?}", e),
+            }
+        }
+
+        if let Some(history) = &self.history
+            && let Err(e) = history.record(loaded.fetched_at, &loaded.moves).await
+        {
+            error!("Failed to record move data version: {:?}", e);
+        }
+    }
+
+    // TODO: No cloning
+    pub fn moves(&self, character: Character) -> Option<Vec<CharacterMove>> {
+        self.loaded.load().moves.get(&character).cloned()
+    }
+
+    /// Moves of every character ready to be suggested while the user is typing
+    pub fn move_index(&self) -> Arc<MoveIndex> {
+        self.loaded.load().index.clone()
+    }
+
+    /// Characters without any data because their moves couldn't be fetched
+    pub fn unavailable_characters(&self) -> HashSet<Character> {
+        self.loaded.load().unavailable.clone()
+    }
+
+    pub fn fetched_at(&self) -> DateTime<Utc> {
+        self.loaded.load().fetched_at
+    }
+
+    /// Whether the data was loaded from a snapshot because the repository couldn't be
+    /// reached
+    pub fn is_stale(&self) -> bool {
+        self.loaded.load().stale
+    }
+
+    /// Checks the current data for missing, unparsable or badly cleaned up values
+    pub fn lint(&self) -> LintReport {
+        LintReport::new(&self.loaded.load().moves)
+    }
+
+    /// Like `lint` for a single character, `None` if there's no data for them
+    pub fn lint_character(&self, character: Character) -> Option<LintReport> {
+        let loaded = self.loaded.load();
+        let moves = loaded.moves.get(&character)?;
+
+        Some(LintReport::for_character(character, moves))
+    }
+
+    /// Past versions of the data, if the store keeps them
+    pub fn history(&self) -> Option<&MoveHistory> {
+        self.history.as_ref()
+    }
+}
+
+/// Retries only if the repository doesn't already, stacked retries would multiply the
+/// requests sent to a source that is struggling
+async fn with_retry<T, F, Fut>(
+    move_repository: &impl MoveRepository,
+    config: &MoveStoreConfig,
+    what: &str,
+    mut fetch: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let attempts = if move_repository.retries_requests() {
+        1
+    } else {
+        config.fetch_attempts
+    };
+    let mut backoff = config.retry_backoff;
+    let mut attempt = 1;
+
+    loop {
+        match fetch().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                warn!("Attempt {attempt} to fetch {what} failed, retrying in {backoff:?}: {e:#}");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use anyhow::{Result, anyhow};
+    use async_trait::async_trait;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+    use crate::repositories::wavu_stand_in::WavuStandIn;
+
+    const FIXTURE_SNAPSHOT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/moves_snapshot.json"
+    );
+
+    #[tokio::test(start_paused = true)]
+    async fn test_falls_back_to_snapshot_when_repository_fails() {
+        let config = MoveStoreConfig {
+            snapshot_path: Some(FIXTURE_SNAPSHOT.into()),
+            ..Default::default()
+        };
+
+        let store = MoveStore::try_new(FailingMoveRepository, config)
+            .await
+            .unwrap();
+
+        assert!(store.is_stale());
+        assert_eq!(store.fetched_at().to_rfc3339(), "2025-10-12T18:30:00+00:00");
+
+        let paul_moves = store.moves(Character::Paul).unwrap();
+        assert_eq!(paul_moves.len(), 2);
+        assert_eq!(paul_moves[1].id, "Paul-qcf+2");
+        assert_eq!(paul_moves[1].alias, vec!["deathfist"]);
+        assert_eq!(paul_moves[1].on_block.as_deref(), Some("-15"));
+        assert!(store.moves(Character::Kazuya).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_without_snapshot_when_repository_fails() {
+        let result = MoveStore::try_new(FailingMoveRepository, MoveStoreConfig::default()).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fails_when_repository_and_snapshot_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = MoveStoreConfig {
+            snapshot_path: Some(dir.path().join("missing.json")),
+            ..Default::default()
+        };
+
+        let result = MoveStore::try_new(FailingMoveRepository, config).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_writes_snapshot_after_successful_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_path = dir.path().join("snapshot.json");
+        let config = MoveStoreConfig {
+            snapshot_path: Some(snapshot_path.clone()),
+            ..Default::default()
+        };
+
+        let store = MoveStore::try_new(StaticMoveRepository, config)
+            .await
+            .unwrap();
+        let snapshot = MoveSnapshot::read(&snapshot_path).await.unwrap();
+
+        assert!(!store.is_stale());
+        assert_eq!(snapshot.fetched_at, store.fetched_at());
+        assert_eq!(snapshot.moves.len(), Character::iter().count());
+        assert_eq!(
+            snapshot.moves[&Character::Bryan],
+            store.moves(Character::Bryan).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_swaps_in_new_data() {
+        let repository = ToggleMoveRepository::default();
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let first_fetch = store.fetched_at();
+
+        store.move_repository.name.store(true, Ordering::SeqCst);
+        store.refresh_moves().await.unwrap();
+
+        let moves = store.moves(Character::Jin).unwrap();
+        assert_eq!(moves[0].name.as_deref(), Some("updated"));
+        assert!(store.fetched_at() >= first_fetch);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_refresh_keeps_old_data() {
+        let repository = ToggleMoveRepository::default();
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let first_fetch = store.fetched_at();
+
+        store.move_repository.fail.store(true, Ordering::SeqCst);
+        let result = store.refresh_moves().await;
+
+        assert!(result.is_err());
+        assert_eq!(store.moves(Character::Jin).unwrap()[0].id, "Jin-1,2");
+        assert_eq!(store.fetched_at(), first_fetch);
+        assert!(!store.is_stale());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refresh_clears_stale_flag() {
+        let repository = ToggleMoveRepository::default();
+        repository.fail.store(true, Ordering::SeqCst);
+        // Refreshing overwrites the snapshot, so give the store a copy of the fixture
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_path = dir.path().join("snapshot.json");
+        std::fs::copy(FIXTURE_SNAPSHOT, &snapshot_path).unwrap();
+        let config = MoveStoreConfig {
+            snapshot_path: Some(snapshot_path),
+            ..Default::default()
+        };
+
+        let store = MoveStore::try_new(repository, config).await.unwrap();
+        assert!(store.is_stale());
+
+        store.move_repository.fail.store(false, Ordering::SeqCst);
+        store.refresh_moves().await.unwrap();
+
+        assert!(!store.is_stale());
+        assert_eq!(store.moves(Character::Jin).unwrap()[0].id, "Jin-1,2");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keeps_characters_that_loaded() {
+        let repository = PartialMoveRepository::failing(&[Character::Kuma, Character::Panda]);
+
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.unavailable_characters(),
+            HashSet::from([Character::Kuma, Character::Panda])
+        );
+        assert!(store.moves(Character::Kuma).is_none());
+        assert!(store.moves(Character::Bryan).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_failed_fetches() {
+        let repository = PartialMoveRepository::failing(&[Character::Kuma]);
+        repository.failures_left.store(2, Ordering::SeqCst);
+
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert!(store.unavailable_characters().is_empty());
+        assert!(store.moves(Character::Kuma).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refresh_fills_in_unavailable_characters() {
+        let repository = PartialMoveRepository::failing(&[Character::Kuma]);
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+        assert!(store.moves(Character::Kuma).is_none());
+
+        store.move_repository.failing.lock().await.clear();
+        store.refresh_moves().await.unwrap();
+
+        assert!(store.unavailable_characters().is_empty());
+        assert!(store.moves(Character::Kuma).is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refresh_keeps_old_data_for_failed_characters() {
+        let repository = PartialMoveRepository::failing(&[]);
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        store
+            .move_repository
+            .failing
+            .lock()
+            .await
+            .insert(Character::Kuma);
+        store.refresh_moves().await.unwrap();
+
+        assert!(store.unavailable_characters().is_empty());
+        assert!(store.moves(Character::Kuma).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_prefers_loading_all_moves_at_once() {
+        let store = MoveStore::try_new(BulkMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store
+                .move_repository
+                .character_requests
+                .load(Ordering::SeqCst),
+            0
+        );
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-bulk");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetches_characters_separately_when_bulk_load_fails() {
+        let repository = BulkMoveRepository::default();
+        repository.fail_bulk.store(true, Ordering::SeqCst);
+
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store
+                .move_repository
+                .character_requests
+                .load(Ordering::SeqCst),
+            Character::iter().count()
+        );
+        assert_eq!(
+            store.move_repository.bulk_requests.load(Ordering::SeqCst),
+            MoveStoreConfig::default().fetch_attempts
+        );
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-1");
+    }
+
+    #[tokio::test]
+    async fn test_characters_missing_from_bulk_load_are_unavailable() {
+        let repository = BulkMoveRepository::default();
+        repository.missing.lock().await.insert(Character::Kuma);
+
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.unavailable_characters(),
+            HashSet::from([Character::Kuma])
+        );
+        assert!(store.moves(Character::Kuma).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_characters_missing_from_bulk_refresh_keep_their_moves() {
+        let store = MoveStore::try_new(BulkMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        store
+            .move_repository
+            .missing
+            .lock()
+            .await
+            .insert(Character::Kuma);
+        let diff = store.refresh_moves().await.unwrap();
+
+        assert!(diff.is_empty());
+        assert!(store.unavailable_characters().is_empty());
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-bulk");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_leaves_retries_to_repositories_that_retry() {
+        let repository = BulkMoveRepository::default();
+        repository.fail_bulk.store(true, Ordering::SeqCst);
+        repository.retries_requests.store(true, Ordering::SeqCst);
+
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.move_repository.bulk_requests.load(Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_request_per_character_when_source_is_unavailable() {
+        let repository = BulkMoveRepository::default();
+        repository.unavailable.store(true, Ordering::SeqCst);
+        let config = MoveStoreConfig {
+            snapshot_path: Some(FIXTURE_SNAPSHOT.into()),
+            ..Default::default()
+        };
+
+        let store = MoveStore::try_new(repository, config).await.unwrap();
+
+        assert!(store.is_stale());
+        assert_eq!(
+            store
+                .move_repository
+                .character_requests
+                .load(Ordering::SeqCst),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_only_fetches_changed_characters() {
+        let store = MoveStore::try_new(FeedMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let loaded_at = store.fetched_at();
+        let repository = &store.move_repository;
+        *repository.changed.lock().await = Some(HashSet::from([Character::Kuma]));
+        repository.requests.lock().await.clear();
+        repository.version.store(2, Ordering::SeqCst);
+
+        store.refresh_moves().await.unwrap();
+
+        assert_eq!(*repository.since.lock().await, Some(loaded_at));
+        assert_eq!(*repository.requests.lock().await, vec![Character::Kuma]);
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-2");
+        assert_eq!(store.moves(Character::Lee).unwrap()[0].id, "Lee-1");
+        assert!(store.fetched_at() > loaded_at);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_fetches_everything_when_change_feed_fails() {
+        let store = MoveStore::try_new(FeedMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let repository = &store.move_repository;
+        repository.requests.lock().await.clear();
+        repository.version.store(2, Ordering::SeqCst);
+
+        store.refresh_moves().await.unwrap();
+
+        assert_eq!(
+            repository.requests.lock().await.len(),
+            Character::iter().count()
+        );
+        assert_eq!(store.moves(Character::Lee).unwrap()[0].id, "Lee-2");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_incremental_refresh_retries_unavailable_characters() {
+        let repository = FeedMoveRepository::default();
+        repository.failing.lock().await.insert(Character::Kuma);
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let repository = &store.move_repository;
+        repository.failing.lock().await.clear();
+        *repository.changed.lock().await = Some(HashSet::new());
+
+        store.refresh_moves().await.unwrap();
+
+        assert!(store.unavailable_characters().is_empty());
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-1");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_incremental_refresh_retries_changed_characters_that_failed() {
+        let store = MoveStore::try_new(FeedMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+        let repository = &store.move_repository;
+        *repository.changed.lock().await = Some(HashSet::from([Character::Kuma]));
+        repository.failing.lock().await.insert(Character::Kuma);
+        repository.version.store(2, Ordering::SeqCst);
+
+        store.refresh_moves().await.unwrap();
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-1");
+
+        // The change is older than the last refresh now, but still hasn't been fetched
+        *repository.changed.lock().await = Some(HashSet::new());
+        repository.failing.lock().await.clear();
+        repository.requests.lock().await.clear();
+        store.refresh_moves().await.unwrap();
+
+        assert_eq!(*repository.requests.lock().await, vec![Character::Kuma]);
+        assert_eq!(store.moves(Character::Kuma).unwrap()[0].id, "Kuma-2");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refresh_fetches_everything_after_starting_from_snapshot() {
+        let repository = FeedMoveRepository::default();
+        repository.failing.lock().await.extend(Character::iter());
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_path = dir.path().join("snapshot.json");
+        std::fs::copy(FIXTURE_SNAPSHOT, &snapshot_path).unwrap();
+        let config = MoveStoreConfig {
+            snapshot_path: Some(snapshot_path),
+            ..Default::default()
+        };
+        let store = MoveStore::try_new(repository, config).await.unwrap();
+        assert!(store.is_stale());
+        let repository = &store.move_repository;
+        repository.failing.lock().await.clear();
+        repository.requests.lock().await.clear();
+        *repository.changed.lock().await = Some(HashSet::new());
+
+        store.refresh_moves().await.unwrap();
+
+        assert_eq!(*repository.since.lock().await, None);
+        assert_eq!(
+            repository.requests.lock().await.len(),
+            Character::iter().count()
+        );
+        assert!(!store.is_stale());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_returns_changes() {
+        let store = MoveStore::try_new(ToggleMoveRepository::default(), MoveStoreConfig::default())
+            .await
+            .unwrap();
+
+        let unchanged = store.refresh_moves().await.unwrap();
+        store.move_repository.name.store(true, Ordering::SeqCst);
+        let renamed = store.refresh_moves().await.unwrap();
+
+        assert!(unchanged.is_empty());
+        assert_eq!(renamed.characters.len(), Character::iter().count());
+        assert_eq!(
+            renamed.character(Character::Lee).unwrap().to_string(),
+            "Lee\n  ~ Lee-1,2: Name none → updated"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_refresh_from_stand_in() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json"]).await;
+        let config = MoveStoreConfig {
+            fetch_attempts: 1,
+            ..Default::default()
+        };
+        let store = MoveStore::try_new(wavu.repository(), config).await.unwrap();
+        wavu.serve_recent_changes("recent_changes.json").await;
+        wavu.serve_fixture(Character::Paul, "paul_page_1.json")
+            .await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+        let requests_before = wavu.request_count().await;
+
+        store.refresh_moves().await.unwrap();
+
+        // The change feed, then every character with the revision of their page. Paul
+        // and Kazuya changed and the rest had no rows in the bulk query
+        assert_eq!(
+            wavu.request_count().await - requests_before,
+            1 + 2 * Character::iter().count()
+        );
+        assert_eq!(store.moves(Character::Paul).unwrap().len(), 2);
+        assert_eq!(store.moves(Character::Kazuya).unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_full_refresh_when_stand_in_change_feed_is_unreadable() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json"]).await;
+        let config = MoveStoreConfig {
+            fetch_attempts: 1,
+            ..Default::default()
+        };
+        let store = MoveStore::try_new(wavu.repository(), config).await.unwrap();
+        wavu.serve_recent_changes_response(
+            None,
+            ResponseTemplate::new(200).set_body_raw("{}", "application/json"),
+        )
+        .await;
+        let requests_before = wavu.request_count().await;
+
+        store.refresh_moves().await.unwrap();
+
+        // The change feed, then the bulk query for everything and its page revisions
+        assert_eq!(wavu.request_count().await - requests_before, 3);
+        assert_eq!(store.moves(Character::Paul).unwrap().len(), 5);
+        assert_eq!(store.moves(Character::Kazuya).unwrap().len(), 5);
+    }
+
+    /// Reports the characters in `changed` as changed, or fails to read its change feed
+    /// if it's `None`. Moves are tagged with `version` and every request is recorded
+    struct FeedMoveRepository {
+        changed: Mutex<Option<HashSet<Character>>>,
+        failing: Mutex<HashSet<Character>>,
+        since: Mutex<Option<DateTime<Utc>>>,
+        requests: Mutex<Vec<Character>>,
+        version: AtomicUsize,
+    }
+
+    impl Default for FeedMoveRepository {
+        fn default() -> Self {
+            Self {
+                changed: Mutex::new(None),
+                failing: Mutex::new(HashSet::new()),
+                since: Mutex::new(None),
+                requests: Mutex::new(Vec::new()),
+                version: AtomicUsize::new(1),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MoveRepository for FeedMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            self.requests.lock().await.push(character);
+            if self.failing.lock().await.contains(&character) {
+                return Err(anyhow!("could not fetch moves for {character}"));
+            }
+
+            let version = self.version.load(Ordering::SeqCst);
+            Ok(vec![CharacterMove {
+                id: format!("{character}-{version}"),
+                ..Default::default()
+            }])
+        }
+
+        async fn changed_characters(
+            &self,
+            since: DateTime<Utc>,
+        ) -> Option<Result<HashSet<Character>>> {
+            *self.since.lock().await = Some(since);
+            let changed = self.changed.lock().await.clone();
+            Some(changed.ok_or_else(|| anyhow!("could not read recent changes")))
+        }
+    }
+
+    /// Loads everything in one go unless `fail_bulk` is set, counting the requests for
+    /// single characters
+    #[derive(Default)]
+    struct BulkMoveRepository {
+        fail_bulk: AtomicBool,
+        /// Fail the bulk load like a source that is rate limiting us
+        unavailable: AtomicBool,
+        retries_requests: AtomicBool,
+        /// Characters the bulk load has no rows for
+        missing: Mutex<HashSet<Character>>,
+        bulk_requests: AtomicUsize,
+        character_requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MoveRepository for BulkMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            self.character_requests.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![CharacterMove {
+                id: format!("{character}-1"),
+                ..Default::default()
+            }])
+        }
+
+        fn retries_requests(&self) -> bool {
+            self.retries_requests.load(Ordering::SeqCst)
+        }
+
+        async fn all_moves(&self) -> Option<Result<MoveMap>> {
+            self.bulk_requests.fetch_add(1, Ordering::SeqCst);
+            if self.unavailable.load(Ordering::SeqCst) {
+                return Some(Err(
+                    SourceUnavailable("got 429 Too Many Requests".into()).into()
+                ));
+            }
+            if self.fail_bulk.load(Ordering::SeqCst) {
+                return Some(Err(anyhow!("could not fetch all moves")));
+            }
+
+            let missing = self.missing.lock().await.clone();
+            Some(Ok(Character::iter()
+                .filter(|character| !missing.contains(character))
+                .map(|character| {
+                    let moves = vec![CharacterMove {
+                        id: format!("{character}-bulk"),
+                        ..Default::default()
+                    }];
+                    (character, moves)
+                })
+                .collect()))
+        }
+    }
+
+    /// Fails for the characters in `failing`. While `failures_left` is above zero the
+    /// failures are temporary and count down with every attempt
+    struct PartialMoveRepository {
+        failing: Mutex<HashSet<Character>>,
+        failures_left: AtomicUsize,
+    }
+
+    impl PartialMoveRepository {
+        fn failing(characters: &[Character]) -> Self {
+            Self {
+                failing: Mutex::new(characters.iter().copied().collect()),
+                failures_left: AtomicUsize::new(usize::MAX),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MoveRepository for PartialMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            if self.failing.lock().await.contains(&character)
+                && self
+                    .failures_left
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok()
+            {
+                return Err(anyhow!("could not fetch moves for {character}"));
+            }
+
+            Ok(vec![CharacterMove {
+                id: format!("{character}-1"),
+                ..Default::default()
+            }])
+        }
+    }
+
+    #[derive(Default)]
+    struct ToggleMoveRepository {
+        fail: AtomicBool,
+        name: AtomicBool,
+    }
+
+    #[async_trait]
+    impl MoveRepository for ToggleMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(anyhow!("could not fetch moves for {character}"));
+            }
+
+            let name = self
+                .name
+                .load(Ordering::SeqCst)
+                .then(|| "updated".to_string());
+
+            Ok(vec![CharacterMove {
+                id: format!("{character}-1,2"),
+                name,
+                ..Default::default()
+            }])
+        }
+    }
+
+    struct FailingMoveRepository;
+
+    #[async_trait]
+    impl MoveRepository for FailingMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            Err(anyhow!("could not fetch moves for {character}"))
+        }
+    }
+
+    struct StaticMoveRepository;
+
+    #[async_trait]
+    impl MoveRepository for StaticMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            Ok(vec![CharacterMove {
+                id: format!("{character}-1,2"),
+                ..Default::default()
+            }])
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::{
+    task::JoinHandle,
+    time::{MissedTickBehavior, interval},
+};
+use tracing::{error, info};
+
+use crate::{
+    move_store::{MoveStore, diff::MoveDiff},
+    repositories::MoveRepository,
+};
+
+/// Gets told about every refresh that changed the move data
+#[async_trait]
+pub trait ChangeListener: Send + Sync {
+    async fn moves_changed(&self, diff: &MoveDiff);
+}
+
+/// For refreshing without telling anyone
+#[async_trait]
+impl ChangeListener for () {
+    async fn moves_changed(&self, _diff: &MoveDiff) {}
+}
+
+#[async_trait]
+impl<T: ChangeListener + ?Sized> ChangeListener for Arc<T> {
+    async fn moves_changed(&self, diff: &MoveDiff) {
+        (**self).moves_changed(diff).await;
+    }
+}
+
+/// Spawns a task that refreshes `store` every `period`. The first refresh happens one
+/// `period` after spawning since the store was just loaded. A failed refresh is logged
+/// and the store keeps serving its current data until the next attempt
+pub fn spawn_periodic_refresh<M, L>(
+    store: Arc<MoveStore<M>>,
+    period: Duration,
+    listener: L,
+) -> JoinHandle<()>
+where
+    M: MoveRepository + 'static,
+    L: ChangeListener + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+
+            info!("Refreshing move data");
+            match store.refresh_moves().await {
+                Ok(diff) if diff.is_empty() => info!("Refreshed move data, nothing changed"),
+                Ok(diff) => {
+                    info!("Refreshed move data, changes:\n{diff}");
+                    listener.moves_changed(&diff).await;
+                }
+                Err(e) => error!("Failed to refresh move data, keeping old data: {:?}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use anyhow::Result;
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        move_store::MoveStoreConfig,
+        tekken::{character::Character, character_move::CharacterMove},
+    };
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refreshes_periodically() {
+        let store = Arc::new(
+            MoveStore::try_new(
+                CountingMoveRepository::default(),
+                MoveStoreConfig::default(),
+            )
+            .await
+            .unwrap(),
+        );
+
+        let handle = spawn_periodic_refresh(store.clone(), Duration::from_secs(60), ());
+
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(store.moves(Character::Lee).unwrap()[0].id, "Lee-0");
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(store.moves(Character::Lee).unwrap()[0].id, "Lee-1");
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(store.moves(Character::Lee).unwrap()[0].id, "Lee-2");
+
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tells_listener_about_changes() {
+        let store = Arc::new(
+            MoveStore::try_new(
+                CountingMoveRepository::default(),
+                MoveStoreConfig::default(),
+            )
+            .await
+            .unwrap(),
+        );
+        let listener = Arc::new(RecordingListener::default());
+
+        let handle = spawn_periodic_refresh(store, Duration::from_secs(60), listener.clone());
+        tokio::time::sleep(Duration::from_secs(90)).await;
+
+        let diffs = listener.diffs.lock().await;
+        assert_eq!(diffs.len(), 1);
+        let lee = diffs[0].character(Character::Lee).unwrap();
+        assert_eq!(lee.added[0].id, "Lee-1");
+        assert_eq!(lee.removed[0].id, "Lee-0");
+
+        handle.abort();
+    }
+
+    #[derive(Default)]
+    struct RecordingListener {
+        diffs: Mutex<Vec<MoveDiff>>,
+    }
+
+    #[async_trait]
+    impl ChangeListener for RecordingListener {
+        async fn moves_changed(&self, diff: &MoveDiff) {
+            self.diffs.lock().await.push(diff.clone());
+        }
+    }
+
+    /// Every full load returns moves tagged with the number of the load
+    #[derive(Default)]
+    struct CountingMoveRepository {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MoveRepository for CountingMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+            let load = calls / Character::iter().count();
+
+            Ok(vec![CharacterMove {
+                id: format!("{character}-{load}"),
+                ..Default::default()
+            }])
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{borrow::Cow, path::Path};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    move_store::MoveMap, storage::write_atomically, tekken::character::deserialize_known_characters,
+};
+
+/// Bump this whenever a change to `CharacterMove` or the snapshot layout makes
+/// older snapshot files unreadable
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// On-disk copy of all move data, written after every successful fetch so the bot
+/// can still start when the move repository is unreachable
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveSnapshot<'a> {
+    pub version: u32,
+    pub fetched_at: DateTime<Utc>,
+    #[serde(deserialize_with = "deserialize_moves")]
+    pub moves: Cow<'a, MoveMap>,
+}
+
+/// Characters that were removed from the registry are left out
+fn deserialize_moves<'de, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Cow<'a, MoveMap>, D::Error> {
+    deserialize_known_characters(deserializer).map(Cow::Owned)
+}
+
+impl<'a> MoveSnapshot<'a> {
+    pub fn new(fetched_at: DateTime<Utc>, moves: &'a MoveMap) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            fetched_at,
+            moves: Cow::Borrowed(moves),
+        }
+    }
+}
+
+impl MoveSnapshot<'static> {
+    pub async fn read(path: &Path) -> Result<Self> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed to read snapshot {}", path.display()))?;
+
+        let snapshot: MoveSnapshot = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse snapshot {}", path.display()))?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            bail!(
+                "snapshot {} has version {}, expected {SNAPSHOT_VERSION}",
+                path.display(),
+                snapshot.version
+            );
+        }
+
+        Ok(snapshot)
+    }
+}
+
+impl MoveSnapshot<'_> {
+    pub async fn write(&self, path: &Path) -> Result<()> {
+        let bytes = serde_json::to_vec(self)?;
+        write_atomically(path, &bytes)
+            .await
+            .with_context(|| format!("failed to write snapshot {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::tekken::{character::Character, character_move::CharacterMove};
+
+    #[tokio::test]
+    async fn test_write_and_read_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("snapshot.json");
+
+        let moves = HashMap::from([(
+            Character::Paul,
+            vec![CharacterMove {
+                id: "Paul-qcf+2".into(),
+                name: Some("Phoenix Smasher".into()),
+                alias: vec!["deathfist".into()],
+                on_block: Some("-15".into()),
+                notes: vec!["Balcony break".into()],
+                ..Default::default()
+            }],
+        )]);
+        let fetched_at = Utc::now();
+
+        MoveSnapshot::new(fetched_at, &moves)
+            .write(&path)
+            .await
+            .unwrap();
+        let snapshot = MoveSnapshot::read(&path).await.unwrap();
+
+        assert_eq!(snapshot.fetched_at, fetched_at);
+        assert_eq!(*snapshot.moves, moves);
+    }
+
+    #[tokio::test]
+    async fn test_read_rejects_other_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        tokio::fs::write(
+            &path,
+            r#"{"version":0,"fetched_at":"2025-01-01T00:00:00Z","moves":{}}"#,
+        )
+        .await
+        .unwrap();
+
+        let result = MoveSnapshot::read(&path).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_skips_unknown_characters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        tokio::fs::write(
+            &path,
+            r#"{"version":1,"fetched_at":"2025-01-01T00:00:00Z","moves":{"Paul":[],"Gon":[]}}"#,
+        )
+        .await
+        .unwrap();
+
+        let snapshot = MoveSnapshot::read(&path).await.unwrap();
+
+        assert_eq!(*snapshot.moves, HashMap::from([(Character::Paul, vec![])]));
+    }
+}
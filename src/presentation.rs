@@ -0,0 +1,22 @@
+use poise::serenity_prelude::{Colour, CreateEmbed};
+use scraper::Html;
+
+use crate::tekken::character::Character;
+
+/// Embed in the character's colour with their portrait
+pub fn character_embed(character: Character) -> CreateEmbed {
+    let embed = CreateEmbed::new().colour(Colour::new(character.colour()));
+    match character.portrait_url() {
+        Some(url) => embed.thumbnail(url),
+        None => embed,
+    }
+}
+
+/// Move name as plain text, without the HTML entities and tags Wavu may leave in it
+pub fn decode_move_name(move_name: &str) -> String {
+    let decoded = html_escape::decode_html_entities(move_name);
+    Html::parse_fragment(&decoded)
+        .root_element()
+        .text()
+        .collect()
+}
@@ -0,0 +1,331 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::info;
+
+use crate::{
+    repositories::MoveRepository,
+    tekken::{
+        character::Character,
+        character_move::{CharacterMove, movelist_problems},
+        provenance::{MoveSource, Provenance},
+    },
+};
+
+/// Reads movelists from a directory with one JSON or TOML file per character, such as
+/// `paul.toml` or `armor-king.json`. The file name is matched against the character's
+/// name ignoring case, spaces and dashes
+pub struct FileMoveRepository {
+    directory: PathBuf,
+}
+
+/// Layout of a movelist file, the same for JSON and TOML
+#[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct MoveListFile {
+    pub moves: Vec<CharacterMove>,
+}
+
+impl FileMoveRepository {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+}
+
+/// Finds the JSON or TOML file for a character in a directory, if there is one
+pub async fn find_character_file(
+    directory: &Path,
+    character: Character,
+) -> Result<Option<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(directory)
+        .await
+        .with_context(|| format!("failed to read {}", directory.display()))?;
+
+    let mut found = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_data_file = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("json" | "toml")
+        );
+        let stem_character = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(Character::from_name);
+
+        if is_data_file && stem_character == Some(character) {
+            found.push(path);
+        }
+    }
+
+    match found.len() {
+        0 | 1 => Ok(found.pop()),
+        _ => bail!(
+            "found more than one file for {character}: {}",
+            found
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Reads a JSON or TOML file depending on its extension
+pub async fn read_data_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+
+    let parsed = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str(&contents).map_err(anyhow::Error::from),
+        _ => serde_json::from_str(&contents).map_err(anyhow::Error::from),
+    };
+
+    parsed.with_context(|| format!("{} is malformed", path.display()))
+}
+
+#[async_trait]
+impl MoveRepository for FileMoveRepository {
+    async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+        let Some(path) = find_character_file(&self.directory, character).await? else {
+            info!(
+                "No movelist for {character} in {}",
+                self.directory.display()
+            );
+            return Ok(Vec::new());
+        };
+
+        let file: MoveListFile = read_data_file(&path).await?;
+        validate_move_list(&path, character, &file.moves)?;
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let provenance = Provenance {
+            source: MoveSource::File(file_name.into_owned()),
+            fetched_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        Ok(file
+            .moves
+            .into_iter()
+            .map(|m| CharacterMove {
+                provenance: provenance.clone(),
+                ..m
+            })
+            .collect())
+    }
+}
+
+/// Reports every problem in the file at once rather than just the first one, so
+/// curators can fix them in one go
+fn validate_move_list(path: &Path, character: Character, moves: &[CharacterMove]) -> Result<()> {
+    let problems = movelist_problems(character, moves)
+        .into_iter()
+        .map(|(i, message)| {
+            let id = &moves[i].id;
+            format!(
+                "{} entry {} ({}): {message}",
+                path.display(),
+                i + 1,
+                if id.is_empty() { "no id" } else { id }
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if !problems.is_empty() {
+        bail!(
+            "movelist for {character} is invalid:\n{}",
+            problems.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::tekken::{character_move::MoveField, frames::FrameField, tracking::Tracking};
+
+    const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/movelists");
+
+    fn full_move() -> CharacterMove {
+        CharacterMove {
+            id: "Paul-qcf+2".into(),
+            name: Some("Phoenix Smasher".into()),
+            input: Some("qcf+2".into()),
+            alias: vec!["deathfist".into()],
+            alt: vec!["Paul-CS.2".into()],
+            parent: Some("Paul-qcf".into()),
+            target: Some("m".into()),
+            damage: Some("30".into()),
+            reach: Some("2.5".into()),
+            tracking: Tracking::new(Some("n"), Some("late")),
+            startup_frames: Some("i14~15".into()),
+            recovery_frames: Some("r34".into()),
+            total_frames: Some("48".into()),
+            crush: Some("cs4~".into()),
+            on_block: Some("-15".into()),
+            on_hit: Some("+31a(+21)".into()),
+            on_counter_hit: Some("+31a(+21)".into()),
+            notes: vec!["Balcony Break".into()],
+            overridden: BTreeSet::from([MoveField::Alias, MoveField::Frames(FrameField::OnBlock)]),
+            provenance: Provenance::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_toml_and_json_movelists() {
+        let repository = FileMoveRepository::new(FIXTURE_DIR);
+
+        let paul = repository.character_moves(Character::Paul).await.unwrap();
+        let armor_king = repository
+            .character_moves(Character::ArmorKing)
+            .await
+            .unwrap();
+
+        assert_eq!(paul.len(), 2);
+        assert_eq!(paul[1].id, "Paul-qcf+2");
+        assert_eq!(paul[1].alias, vec!["deathfist"]);
+        assert_eq!(paul[1].tracking, Tracking::new(Some("n"), None));
+        assert_eq!(armor_king.len(), 1);
+        assert_eq!(armor_king[0].id, "Armor King-df+1");
+    }
+
+    #[tokio::test]
+    async fn test_moves_name_their_file() {
+        let repository = FileMoveRepository::new(FIXTURE_DIR);
+
+        let paul = repository.character_moves(Character::Paul).await.unwrap();
+
+        let provenance = &paul[0].provenance;
+        assert_eq!(provenance.source, MoveSource::File("paul.toml".into()));
+        assert!(provenance.fetched_at.is_some());
+        assert_eq!(provenance.revision, None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_movelist_is_empty() {
+        let repository = FileMoveRepository::new(FIXTURE_DIR);
+
+        let moves = repository.character_moves(Character::Zafina).await.unwrap();
+
+        assert!(moves.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_round_trips_every_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = MoveListFile {
+            moves: vec![full_move()],
+        };
+        std::fs::write(
+            dir.path().join("paul.toml"),
+            toml::to_string(&file).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("jack-8.json"),
+            serde_json::to_string(&MoveListFile {
+                moves: vec![CharacterMove {
+                    id: "Jack-8-qcf+2".into(),
+                    ..full_move()
+                }],
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        let repository = FileMoveRepository::new(dir.path());
+
+        let paul = repository.character_moves(Character::Paul).await.unwrap();
+        let jack = repository.character_moves(Character::Jack8).await.unwrap();
+
+        assert_eq!(
+            paul,
+            vec![CharacterMove {
+                provenance: paul[0].provenance.clone(),
+                ..full_move()
+            }]
+        );
+        assert_eq!(jack[0].tracking, full_move().tracking);
+        assert_eq!(jack[0].on_hit, full_move().on_hit);
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_name_file_and_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lee.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[moves]]
+            id = "Lee-1"
+            on_block = "+1"
+
+            [[moves]]
+            id = "Lee-1"
+
+            [[moves]]
+            id = "Paul-2"
+            on_block = "+5x"
+            target = "q"
+            "#,
+        )
+        .unwrap();
+        let repository = FileMoveRepository::new(dir.path());
+
+        let error = repository
+            .character_moves(Character::Lee)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        let file = path.display();
+        assert!(error.contains(&format!(
+            "{file} entry 2 (Lee-1): id is used more than once"
+        )));
+        assert!(error.contains(&format!(
+            "{file} entry 3 (Paul-2): id doesn't start with \"Lee-\""
+        )));
+        assert!(error.contains(&format!(
+            "{file} entry 3 (Paul-2): On Block: could not parse"
+        )));
+        assert!(error.contains(&format!(
+            "{file} entry 3 (Paul-2): Hit Level: could not parse"
+        )));
+        assert!(!error.contains("entry 1"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_file_names_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("law.json");
+        std::fs::write(&path, r#"{"moves": [{"id": 5}]}"#).unwrap();
+        let repository = FileMoveRepository::new(dir.path());
+
+        let error = repository
+            .character_moves(Character::Law)
+            .await
+            .unwrap_err();
+
+        assert!(format!("{error:#}").contains(&path.display().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_more_than_one_file_for_a_character() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lili.json"), r#"{"moves": []}"#).unwrap();
+        std::fs::write(dir.path().join("Lili.toml"), "moves = []").unwrap();
+        let repository = FileMoveRepository::new(dir.path());
+
+        let result = repository.character_moves(Character::Lili).await;
+
+        assert!(result.is_err());
+    }
+}
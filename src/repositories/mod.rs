@@ -1,11 +1,87 @@
+use std::{collections::HashSet, fmt::Display};
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
-use crate::tekken::{character::Character, character_move::CharacterMove};
+use crate::{
+    move_store::MoveMap,
+    tekken::{character::Character, character_move::CharacterMove},
+};
 use anyhow::Result;
 
+pub mod file_move_repository;
+pub mod overriding_move_repository;
+pub mod wavu_client;
 pub mod wavu_move_repository;
+#[cfg(test)]
+pub mod wavu_stand_in;
+pub mod wavu_wikitext;
+
+/// The source of the moves is rate limiting us or down, even after the retries the
+/// repository already made. More requests right away would only add to its load
+#[derive(Debug)]
+pub struct SourceUnavailable(pub String);
 
 #[async_trait]
 pub trait MoveRepository: Send + Sync {
     async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>>;
+
+    /// Whether failed requests are already retried by the repository itself, callers
+    /// then shouldn't retry on top of that
+    fn retries_requests(&self) -> bool {
+        false
+    }
+
+    /// Moves of every character at once, for repositories that can load them in fewer
+    /// requests than one per character. Returns `None` if the repository can't, callers
+    /// then fall back to `character_moves`
+    async fn all_moves(&self) -> Option<Result<MoveMap>> {
+        None
+    }
+
+    /// Characters whose moves changed since `since`, so a refresh only has to fetch
+    /// those. Returns `None` if the repository can't tell, callers then fetch everything
+    async fn changed_characters(
+        &self,
+        _since: DateTime<Utc>,
+    ) -> Option<Result<HashSet<Character>>> {
+        None
+    }
+
+    /// Names of the characters the repository has moves for, including ones missing
+    /// from the character registry. Returns `None` if the repository can't list them
+    async fn character_names(&self) -> Option<Result<Vec<String>>> {
+        None
+    }
+}
+
+#[async_trait]
+impl<T: MoveRepository + ?Sized> MoveRepository for Box<T> {
+    async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+        (**self).character_moves(character).await
+    }
+
+    fn retries_requests(&self) -> bool {
+        (**self).retries_requests()
+    }
+
+    async fn all_moves(&self) -> Option<Result<MoveMap>> {
+        (**self).all_moves().await
+    }
+
+    async fn changed_characters(&self, since: DateTime<Utc>) -> Option<Result<HashSet<Character>>> {
+        (**self).changed_characters(since).await
+    }
+
+    async fn character_names(&self) -> Option<Result<Vec<String>>> {
+        (**self).character_names().await
+    }
+}
+
+impl Display for SourceUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
+
+impl std::error::Error for SourceUnavailable {}
@@ -0,0 +1,587 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::{
+    move_store::MoveMap,
+    repositories::{
+        MoveRepository,
+        file_move_repository::{find_character_file, read_data_file},
+    },
+    tekken::{
+        character::Character,
+        character_move::{CharacterMove, MoveField},
+        frames::FrameField,
+        tracking::Tracking,
+    },
+};
+
+/// Applies community overrides on top of the moves of another repository, for when Wavu
+/// lags behind a patch or the scene disagrees with an entry
+pub struct OverridingMoveRepository<B: MoveRepository, O: OverrideSource> {
+    base: B,
+    overrides: O,
+}
+
+#[async_trait]
+pub trait OverrideSource: Send + Sync {
+    async fn character_overrides(&self, character: Character) -> Result<Vec<MoveOverride>>;
+
+    /// Characters whose overrides were edited, added or removed since `since`. Returns
+    /// `None` if the source can't tell, callers then fetch everything
+    async fn changed_characters(
+        &self,
+        _since: DateTime<Utc>,
+    ) -> Option<Result<HashSet<Character>>> {
+        None
+    }
+}
+
+/// Reads overrides from a directory with one JSON or TOML file per character, named like
+/// the files of `FileMoveRepository`
+pub struct FileOverrideSource {
+    directory: PathBuf,
+    /// Characters that had an override file when last looked at, so removing a file
+    /// counts as a change
+    known: Mutex<HashSet<Character>>,
+}
+
+/// A change to a single move. Fields that are set replace the move's value
+#[derive(Debug, PartialEq, Eq, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct MoveOverride {
+    pub id: String,
+    /// Adds the move if it doesn't exist yet
+    pub add: bool,
+    /// Removes the move
+    pub hide: bool,
+    /// Added to the move's aliases rather than replacing them
+    pub aliases: Vec<String>,
+    pub name: Option<String>,
+    pub input: Option<String>,
+    pub target: Option<String>,
+    pub damage: Option<String>,
+    pub reach: Option<String>,
+    pub tracking: Option<Tracking>,
+    pub startup_frames: Option<String>,
+    pub recovery_frames: Option<String>,
+    pub total_frames: Option<String>,
+    pub crush: Option<String>,
+    pub on_block: Option<String>,
+    pub on_hit: Option<String>,
+    pub on_counter_hit: Option<String>,
+    pub notes: Option<Vec<String>>,
+    /// When the override was last edited, set by the source if it knows
+    #[serde(skip)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct OverrideFile {
+    overrides: Vec<MoveOverride>,
+}
+
+impl<B: MoveRepository, O: OverrideSource> OverridingMoveRepository<B, O> {
+    pub fn new(base: B, overrides: O) -> Self {
+        Self { base, overrides }
+    }
+}
+
+impl<B: MoveRepository, O: OverrideSource> OverridingMoveRepository<B, O> {
+    async fn with_overrides(
+        &self,
+        character: Character,
+        mut moves: Vec<CharacterMove>,
+    ) -> Result<Vec<CharacterMove>> {
+        let overrides = self
+            .overrides
+            .character_overrides(character)
+            .await
+            .with_context(|| format!("failed to load overrides for {character}"))?;
+
+        for id in apply_overrides(&mut moves, &overrides) {
+            warn!("Override for {id} doesn't match any move of {character}");
+        }
+
+        Ok(moves)
+    }
+}
+
+#[async_trait]
+impl<B: MoveRepository, O: OverrideSource> MoveRepository for OverridingMoveRepository<B, O> {
+    async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+        let moves = self.base.character_moves(character).await?;
+        self.with_overrides(character, moves).await
+    }
+
+    fn retries_requests(&self) -> bool {
+        self.base.retries_requests()
+    }
+
+    async fn all_moves(&self) -> Option<Result<MoveMap>> {
+        let move_map = match self.base.all_moves().await? {
+            Ok(move_map) => move_map,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut overridden = MoveMap::new();
+        for (character, moves) in move_map {
+            match self.with_overrides(character, moves).await {
+                Ok(moves) => overridden.insert(character, moves),
+                Err(e) => return Some(Err(e)),
+            };
+        }
+
+        Some(Ok(overridden))
+    }
+
+    /// Changes to the base data and to the overrides. If either can't tell what
+    /// changed, neither can the repository
+    async fn changed_characters(&self, since: DateTime<Utc>) -> Option<Result<HashSet<Character>>> {
+        let mut changed = match self.base.changed_characters(since).await? {
+            Ok(changed) => changed,
+            Err(e) => return Some(Err(e)),
+        };
+        match self.overrides.changed_characters(since).await? {
+            Ok(overridden) => changed.extend(overridden),
+            Err(e) => return Some(Err(e)),
+        }
+
+        Some(Ok(changed))
+    }
+
+    async fn character_names(&self) -> Option<Result<Vec<String>>> {
+        self.base.character_names().await
+    }
+}
+
+impl FileOverrideSource {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            known: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Characters with an override file and when it was last modified
+    async fn modified_files(&self) -> Result<HashMap<Character, DateTime<Utc>>> {
+        let mut entries = tokio::fs::read_dir(&self.directory)
+            .await
+            .with_context(|| format!("failed to read {}", self.directory.display()))?;
+
+        let mut modified = HashMap::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_data_file = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("json" | "toml")
+            );
+            let character = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(Character::from_name);
+
+            if is_data_file && let Some(character) = character {
+                let modified_at = entry.metadata().await?.modified()?;
+                modified.insert(character, modified_at.into());
+            }
+        }
+
+        Ok(modified)
+    }
+}
+
+#[async_trait]
+impl OverrideSource for FileOverrideSource {
+    async fn character_overrides(&self, character: Character) -> Result<Vec<MoveOverride>> {
+        let Some(path) = find_character_file(&self.directory, character).await? else {
+            self.known.lock().await.remove(&character);
+            return Ok(Vec::new());
+        };
+        self.known.lock().await.insert(character);
+
+        let file: OverrideFile = read_data_file(&path).await?;
+        let updated_at = tokio::fs::metadata(&path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .ok();
+
+        Ok(file
+            .overrides
+            .into_iter()
+            .map(|o| MoveOverride { updated_at, ..o })
+            .collect())
+    }
+
+    async fn changed_characters(&self, since: DateTime<Utc>) -> Option<Result<HashSet<Character>>> {
+        let modified = match self.modified_files().await {
+            Ok(modified) => modified,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut known = self.known.lock().await;
+        let mut changed = modified
+            .iter()
+            .filter(|(_, modified_at)| **modified_at > since)
+            .map(|(character, _)| *character)
+            .collect::<HashSet<_>>();
+        changed.extend(
+            known
+                .iter()
+                .filter(|character| !modified.contains_key(character)),
+        );
+        *known = modified.into_keys().collect();
+
+        Some(Ok(changed))
+    }
+}
+
+/// Returns the ids of overrides whose move doesn't exist, e.g. because Wavu renamed it
+pub fn apply_overrides(moves: &mut Vec<CharacterMove>, overrides: &[MoveOverride]) -> Vec<String> {
+    let mut unmatched = Vec::new();
+
+    for o in overrides {
+        if o.hide {
+            let count = moves.len();
+            moves.retain(|m| m.id != o.id);
+            if moves.len() == count {
+                unmatched.push(o.id.clone());
+            }
+            continue;
+        }
+
+        match moves.iter_mut().find(|m| m.id == o.id) {
+            Some(character_move) => o.apply(character_move),
+            None if o.add => {
+                let mut character_move = CharacterMove {
+                    id: o.id.clone(),
+                    ..Default::default()
+                };
+                o.apply(&mut character_move);
+                moves.push(character_move);
+            }
+            None => unmatched.push(o.id.clone()),
+        }
+    }
+
+    unmatched
+}
+
+impl MoveOverride {
+    fn apply(&self, m: &mut CharacterMove) {
+        let mut set = |field, target: &mut Option<String>, value: &Option<String>| {
+            if value.is_some() {
+                target.clone_from(value);
+                m.overridden.insert(field);
+            }
+        };
+
+        set(MoveField::Name, &mut m.name, &self.name);
+        set(MoveField::Input, &mut m.input, &self.input);
+        set(MoveField::HitLevel, &mut m.target, &self.target);
+        set(MoveField::Damage, &mut m.damage, &self.damage);
+        set(MoveField::Reach, &mut m.reach, &self.reach);
+        set(MoveField::Crush, &mut m.crush, &self.crush);
+        let frames = [
+            (
+                FrameField::Startup,
+                &mut m.startup_frames,
+                &self.startup_frames,
+            ),
+            (
+                FrameField::Recovery,
+                &mut m.recovery_frames,
+                &self.recovery_frames,
+            ),
+            (FrameField::Total, &mut m.total_frames, &self.total_frames),
+            (FrameField::OnBlock, &mut m.on_block, &self.on_block),
+            (FrameField::OnHit, &mut m.on_hit, &self.on_hit),
+            (
+                FrameField::OnCounterHit,
+                &mut m.on_counter_hit,
+                &self.on_counter_hit,
+            ),
+        ];
+        for (field, target, value) in frames {
+            set(MoveField::Frames(field), target, value);
+        }
+
+        if let Some(tracking) = &self.tracking {
+            m.tracking = tracking.clone();
+            m.overridden.insert(MoveField::Tracking);
+        }
+        if let Some(notes) = &self.notes {
+            m.notes = notes.clone();
+            m.overridden.insert(MoveField::Notes);
+        }
+
+        let new_aliases = self
+            .aliases
+            .iter()
+            .filter(|alias| !m.alias.contains(alias))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !new_aliases.is_empty() {
+            m.alias.extend(new_aliases);
+            m.overridden.insert(MoveField::Alias);
+        }
+
+        if self.updated_at.is_some() {
+            m.provenance.overridden_at = self.updated_at;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::move_store::{MoveStore, MoveStoreConfig};
+    use crate::tekken::tracking::TrackingSide;
+
+    const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/overrides");
+
+    struct StaticMoveRepository;
+
+    #[async_trait]
+    impl MoveRepository for StaticMoveRepository {
+        async fn character_moves(&self, _character: Character) -> Result<Vec<CharacterMove>> {
+            Ok(base_moves())
+        }
+
+        async fn all_moves(&self) -> Option<Result<MoveMap>> {
+            Some(Ok(MoveMap::from([(Character::Paul, base_moves())])))
+        }
+
+        async fn changed_characters(
+            &self,
+            _since: DateTime<Utc>,
+        ) -> Option<Result<HashSet<Character>>> {
+            Some(Ok(HashSet::new()))
+        }
+    }
+
+    struct StaticOverrideSource(Vec<MoveOverride>);
+
+    #[async_trait]
+    impl OverrideSource for StaticOverrideSource {
+        async fn character_overrides(&self, _character: Character) -> Result<Vec<MoveOverride>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn base_moves() -> Vec<CharacterMove> {
+        vec![
+            CharacterMove {
+                id: "Paul-qcf+2".into(),
+                alias: vec!["deathfist".into()],
+                target: Some("m".into()),
+                damage: Some("30".into()),
+                on_block: Some("-15".into()),
+                on_hit: Some("+31a(+21)".into()),
+                ..Default::default()
+            },
+            CharacterMove {
+                id: "Paul-b+1".into(),
+                on_block: Some("-7".into()),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_patch_marks_overridden_fields() {
+        let mut moves = base_moves();
+        let overrides = vec![MoveOverride {
+            id: "Paul-qcf+2".into(),
+            on_block: Some("-13".into()),
+            damage: Some("32".into()),
+            ..Default::default()
+        }];
+
+        let unmatched = apply_overrides(&mut moves, &overrides);
+
+        assert!(unmatched.is_empty());
+        assert_eq!(moves[0].on_block.as_deref(), Some("-13"));
+        assert_eq!(moves[0].damage.as_deref(), Some("32"));
+        assert_eq!(moves[0].on_hit.as_deref(), Some("+31a(+21)"));
+        assert!(moves[0].is_overridden(MoveField::Frames(FrameField::OnBlock)));
+        assert!(moves[0].is_overridden(MoveField::Damage));
+        assert!(!moves[0].is_overridden(MoveField::Frames(FrameField::OnHit)));
+        assert!(moves[1].overridden.is_empty());
+    }
+
+    #[test]
+    fn test_patch_records_when_override_was_edited() {
+        let mut moves = base_moves();
+        let updated_at = "2025-10-12T18:30:00Z".parse().unwrap();
+        let overrides = vec![MoveOverride {
+            id: "Paul-qcf+2".into(),
+            on_block: Some("-13".into()),
+            updated_at: Some(updated_at),
+            ..Default::default()
+        }];
+
+        apply_overrides(&mut moves, &overrides);
+
+        assert_eq!(moves[0].provenance.overridden_at, Some(updated_at));
+        assert_eq!(moves[1].provenance.overridden_at, None);
+    }
+
+    #[test]
+    fn test_add_alias_and_hide() {
+        let mut moves = base_moves();
+        let overrides = vec![
+            MoveOverride {
+                id: "Paul-qcf+2".into(),
+                aliases: vec!["deathfist".into(), "df2".into()],
+                ..Default::default()
+            },
+            MoveOverride {
+                id: "Paul-b+1".into(),
+                hide: true,
+                ..Default::default()
+            },
+            MoveOverride {
+                id: "Paul-d+1+2".into(),
+                add: true,
+                target: Some("l".into()),
+                startup_frames: Some("i21".into()),
+                ..Default::default()
+            },
+        ];
+
+        let unmatched = apply_overrides(&mut moves, &overrides);
+
+        assert!(unmatched.is_empty());
+        let ids = moves.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["Paul-qcf+2", "Paul-d+1+2"]);
+        assert_eq!(moves[0].alias, vec!["deathfist", "df2"]);
+        assert!(moves[0].is_overridden(MoveField::Alias));
+        assert!(moves[1].is_overridden(MoveField::HitLevel));
+        assert!(moves[1].is_overridden(MoveField::Frames(FrameField::Startup)));
+    }
+
+    #[test]
+    fn test_reports_overrides_without_a_move() {
+        let mut moves = base_moves();
+        let overrides = vec![
+            MoveOverride {
+                id: "Paul-qcf+1".into(),
+                on_block: Some("-10".into()),
+                ..Default::default()
+            },
+            MoveOverride {
+                id: "Paul-f+2".into(),
+                hide: true,
+                ..Default::default()
+            },
+        ];
+
+        let unmatched = apply_overrides(&mut moves, &overrides);
+
+        assert_eq!(unmatched, vec!["Paul-qcf+1", "Paul-f+2"]);
+        assert_eq!(moves, base_moves());
+    }
+
+    #[tokio::test]
+    async fn test_repository_applies_overrides_to_base() {
+        let repository = OverridingMoveRepository::new(
+            StaticMoveRepository,
+            StaticOverrideSource(vec![MoveOverride {
+                id: "Paul-b+1".into(),
+                on_block: Some("-5".into()),
+                ..Default::default()
+            }]),
+        );
+
+        let moves = repository.character_moves(Character::Paul).await.unwrap();
+
+        assert_eq!(moves[1].on_block.as_deref(), Some("-5"));
+        assert!(moves[1].is_overridden(MoveField::Frames(FrameField::OnBlock)));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_load_applies_overrides() {
+        let repository = OverridingMoveRepository::new(
+            StaticMoveRepository,
+            StaticOverrideSource(vec![MoveOverride {
+                id: "Paul-b+1".into(),
+                hide: true,
+                ..Default::default()
+            }]),
+        );
+
+        let moves = repository.all_moves().await.unwrap().unwrap();
+
+        assert_eq!(moves[&Character::Paul].len(), 1);
+        assert_eq!(moves[&Character::Paul][0].id, "Paul-qcf+2");
+    }
+
+    #[tokio::test]
+    async fn test_reads_overrides_from_files() {
+        let source = FileOverrideSource::new(FIXTURE_DIR);
+
+        let paul = source.character_overrides(Character::Paul).await.unwrap();
+        let kazuya = source.character_overrides(Character::Kazuya).await.unwrap();
+
+        assert_eq!(paul.len(), 3);
+        assert_eq!(paul[0].on_block.as_deref(), Some("-13"));
+        assert_eq!(
+            paul[0].tracking.as_ref().unwrap().left,
+            Some(TrackingSide::Tracks)
+        );
+        assert!(paul[1].hide);
+        assert!(paul[2].add);
+        assert!(paul.iter().all(|o| o.updated_at.is_some()));
+        assert!(kazuya.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_picks_up_edited_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("paul.toml");
+        let write_override = |on_block: &str| {
+            std::fs::write(
+                &path,
+                format!("[[overrides]]\nid = \"Paul-qcf+2\"\non_block = \"{on_block}\"\n"),
+            )
+            .unwrap()
+        };
+        let on_block =
+            |store: &MoveStore<_>| store.moves(Character::Paul).unwrap()[0].on_block.clone();
+        write_override("-13");
+        let repository = OverridingMoveRepository::new(
+            StaticMoveRepository,
+            FileOverrideSource::new(dir.path()),
+        );
+        let store = MoveStore::try_new(repository, MoveStoreConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(on_block(&store).as_deref(), Some("-13"));
+
+        // File times come from a coarser clock than the store's
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        write_override("-11");
+        let diff = store.refresh_moves().await.unwrap();
+
+        assert!(diff.character(Character::Paul).is_some());
+        assert_eq!(on_block(&store).as_deref(), Some("-11"));
+
+        std::fs::remove_file(&path).unwrap();
+        store.refresh_moves().await.unwrap();
+
+        assert_eq!(on_block(&store).as_deref(), Some("-15"));
+    }
+}
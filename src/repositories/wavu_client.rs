@@ -0,0 +1,543 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Result, bail};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::{
+    StatusCode, Url,
+    header::{self, HeaderMap},
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::{Mutex, Semaphore};
+use tracing::{debug, warn};
+
+use crate::repositories::SourceUnavailable;
+
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (Tekken 8 frame data Discord bot)"
+);
+
+/// Longest Retry-After we're willing to sit through, anything longer is treated as a
+/// failure so a refresh can't hang for hours
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone)]
+pub struct WavuClientConfig {
+    /// Identifies the bot to the wiki's operators, should include a way to contact us
+    pub user_agent: String,
+    /// Limit for a whole request including reading the response
+    pub timeout: Duration,
+    /// How many requests can be in flight at the same time
+    pub max_concurrent_requests: usize,
+    /// Retries after a 429 or 5xx response, a timeout or a failed connection
+    pub max_retries: usize,
+    /// Wait before the first retry if the response has no Retry-After, doubled after
+    /// every retry
+    pub retry_backoff: Duration,
+}
+
+impl Default for WavuClientConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.into(),
+            timeout: Duration::from_secs(30),
+            max_concurrent_requests: 4,
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// HTTP client for the Wavu API that tries to be a well-behaved bot. It's shared by all
+/// requests of a repository, throttles them, backs off when the wiki is struggling and
+/// revalidates responses it has seen before instead of downloading them again
+#[derive(Debug)]
+pub struct WavuClient {
+    http: reqwest::Client,
+    config: WavuClientConfig,
+    permits: Semaphore,
+    cache: Mutex<HashMap<Url, CachedResponse>>,
+}
+
+struct RawResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Bytes,
+}
+
+impl WavuClient {
+    pub fn new(config: WavuClientConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent(&config.user_agent)
+            .timeout(config.timeout)
+            .connect_timeout(config.timeout)
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self {
+            http,
+            permits: Semaphore::new(config.max_concurrent_requests.max(1)),
+            config,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fetches `url` with `params`, revalidating the response on later requests. Meant
+    /// for requests that are sent again with the same parameters
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &[(&str, String)],
+    ) -> Result<T> {
+        let url = Url::parse_with_params(url, params)?;
+        let body = self.get(url, true).await?;
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Like `get_json` without keeping the response, for requests whose parameters
+    /// change every time so the cache doesn't fill up with responses never asked for again
+    pub async fn get_json_uncached<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        params: &[(&str, String)],
+    ) -> Result<T> {
+        let url = Url::parse_with_params(url, params)?;
+        let body = self.get(url, false).await?;
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn get(&self, url: Url, cache: bool) -> Result<Bytes> {
+        let mut backoff = self.config.retry_backoff;
+        let mut retry = 0;
+
+        loop {
+            let permit = self.permits.acquire().await?;
+            let response = match self.conditional_request(&url).await {
+                Ok(response) => response,
+                Err(e) if is_transient(&e) && retry < self.config.max_retries => {
+                    warn!("Request to {url} failed, retrying in {backoff:?}: {e}");
+
+                    drop(permit);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    retry += 1;
+                    continue;
+                }
+                Err(e) if is_transient(&e) => {
+                    return Err(SourceUnavailable(format!(
+                        "request to {url} failed after {retry} retries: {e}"
+                    ))
+                    .into());
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let status = response.status;
+
+            if status == StatusCode::NOT_MODIFIED {
+                if let Some(cached) = self.cache.lock().await.get(&url) {
+                    debug!("{url} is unchanged, using cached response");
+                    return Ok(cached.body.clone());
+                }
+                bail!("got 304 Not Modified for {url} without a cached response");
+            }
+
+            let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            if retryable && retry < self.config.max_retries {
+                let wait = match retry_after(&response.headers, Utc::now()) {
+                    Some(wait) if wait > MAX_RETRY_AFTER => {
+                        return Err(SourceUnavailable(format!(
+                            "{url} asked us to retry after {wait:?}, giving up"
+                        ))
+                        .into());
+                    }
+                    Some(wait) => wait,
+                    None => backoff,
+                };
+                warn!("Got {status} for {url}, retrying in {wait:?}");
+
+                // Waiting shouldn't hold up requests that could go through
+                drop(permit);
+                tokio::time::sleep(wait).await;
+                backoff *= 2;
+                retry += 1;
+                continue;
+            }
+            if retryable {
+                return Err(SourceUnavailable(format!(
+                    "got {status} for {url} after {retry} retries"
+                ))
+                .into());
+            }
+            if !status.is_success() {
+                bail!("got {status} for {url}");
+            }
+
+            let etag = header_value(&response.headers, header::ETAG);
+            let last_modified = header_value(&response.headers, header::LAST_MODIFIED);
+
+            if cache && (etag.is_some() || last_modified.is_some()) {
+                self.cache.lock().await.insert(
+                    url,
+                    CachedResponse {
+                        etag,
+                        last_modified,
+                        body: response.body.clone(),
+                    },
+                );
+            }
+
+            return Ok(response.body);
+        }
+    }
+
+    /// Sends the request and reads the whole response, so a timeout while reading the
+    /// body is retried like one while connecting
+    async fn conditional_request(&self, url: &Url) -> reqwest::Result<RawResponse> {
+        let mut request = self.http.get(url.clone());
+
+        if let Some(cached) = self.cache.lock().await.get(url) {
+            if let Some(etag) = &cached.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await?;
+        Ok(RawResponse {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?,
+        })
+    }
+}
+
+impl Default for WavuClient {
+    fn default() -> Self {
+        Self::new(WavuClientConfig::default())
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+/// Timeouts and failed connections are worth retrying, other errors won't go away
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect()
+}
+
+/// Retry-After is either a number of seconds or an HTTP date
+fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = header_value(headers, header::RETRY_AFTER)?;
+
+    if let Ok(seconds) = value.trim().parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(&value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rstest::rstest;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header as header_is, header_exists, method},
+    };
+
+    use super::*;
+
+    fn fast_client(max_concurrent_requests: usize) -> WavuClient {
+        WavuClient::new(WavuClientConfig {
+            max_concurrent_requests,
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        })
+    }
+
+    fn json(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(body.to_string(), "application/json")
+    }
+
+    #[rstest]
+    #[case("120", Some(Duration::from_secs(120)))]
+    #[case(" 0 ", Some(Duration::ZERO))]
+    #[case("Sun, 12 Oct 2025 18:30:30 GMT", Some(Duration::from_secs(30)))]
+    #[case("Sun, 12 Oct 2025 18:00:00 GMT", Some(Duration::ZERO))]
+    #[case("soon", None)]
+    fn test_retry_after(#[case] value: &str, #[case] expected: Option<Duration>) {
+        let now = "2025-10-12T18:30:00Z".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, value.parse().unwrap());
+
+        assert_eq!(retry_after(&headers, now), expected);
+    }
+
+    #[tokio::test]
+    async fn test_sends_user_agent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header_is("User-Agent", DEFAULT_USER_AGENT))
+            .respond_with(json("{}"))
+            .mount(&server)
+            .await;
+
+        let result = fast_client(1)
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[case(ResponseTemplate::new(429).insert_header("Retry-After", "0"))]
+    #[case(ResponseTemplate::new(503))]
+    #[tokio::test]
+    async fn test_retries_throttled_and_failed_requests(#[case] failure: ResponseTemplate) {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(failure)
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(json(r#"{"ok": true}"#))
+            .mount(&server)
+            .await;
+
+        let body = fast_client(1)
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await
+            .unwrap();
+
+        assert_eq!(body["ok"], true);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let result = fast_client(1)
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await;
+
+        assert!(result.unwrap_err().is::<SourceUnavailable>());
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_retries_timed_out_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(json("{}").set_delay(Duration::from_secs(1)))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(json(r#"{"ok": true}"#))
+            .mount(&server)
+            .await;
+        let client = WavuClient::new(WavuClientConfig {
+            timeout: Duration::from_millis(50),
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        });
+
+        let body = client
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await
+            .unwrap();
+
+        assert_eq!(body["ok"], true);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_requests_keep_timing_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(json("{}").set_delay(Duration::from_secs(1)))
+            .mount(&server)
+            .await;
+        let client = WavuClient::new(WavuClientConfig {
+            timeout: Duration::from_millis(50),
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        });
+
+        let result = client
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await;
+
+        assert!(result.unwrap_err().is::<SourceUnavailable>());
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_server_is_unreachable() {
+        // Nothing listens on a port once its listener is gone
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let result = fast_client(1)
+            .get_json::<serde_json::Value>(&uri, &[])
+            .await;
+
+        assert!(result.unwrap_err().is::<SourceUnavailable>());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_long_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+            .mount(&server)
+            .await;
+
+        let result = fast_client(1)
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await;
+
+        assert!(result.unwrap_err().is::<SourceUnavailable>());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revalidates_cached_responses() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header_is("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(json(r#"{"version": 1}"#).insert_header("ETag", "\"v1\""))
+            .mount(&server)
+            .await;
+        let client = fast_client(1);
+        let params = [("offset", "0".to_string())];
+
+        let first = client
+            .get_json::<serde_json::Value>(&server.uri(), &params)
+            .await
+            .unwrap();
+        let second = client
+            .get_json::<serde_json::Value>(&server.uri(), &params)
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].headers.contains_key("If-None-Match"));
+        assert!(requests[1].headers.contains_key("If-None-Match"));
+    }
+
+    #[tokio::test]
+    async fn test_uncached_requests_are_not_revalidated() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(json(r#"{"version": 1}"#).insert_header("ETag", "\"v1\""))
+            .mount(&server)
+            .await;
+        let client = fast_client(1);
+
+        for _ in 0..2 {
+            client
+                .get_json_uncached::<serde_json::Value>(&server.uri(), &[])
+                .await
+                .unwrap();
+        }
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(
+            requests
+                .iter()
+                .all(|r| !r.headers.contains_key("If-None-Match"))
+        );
+        assert!(client.cache.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sends_if_modified_since() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                json(r#"{"version": 1}"#)
+                    .insert_header("Last-Modified", "Sun, 12 Oct 2025 18:30:00 GMT"),
+            )
+            .mount(&server)
+            .await;
+        let client = fast_client(1);
+
+        client
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await
+            .unwrap();
+        let second = client
+            .get_json::<serde_json::Value>(&server.uri(), &[])
+            .await
+            .unwrap();
+
+        assert_eq!(second["version"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_limits_concurrent_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(json("{}").set_delay(Duration::from_millis(100)))
+            .mount(&server)
+            .await;
+        let client = fast_client(1);
+        let uri = server.uri();
+
+        let start = Instant::now();
+        let results = futures::future::join_all(
+            (0..3).map(|_| client.get_json::<serde_json::Value>(&uri, &[])),
+        )
+        .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert!(start.elapsed() >= Duration::from_millis(300));
+    }
+}
@@ -1,64 +1,397 @@
-use std::sync::LazyLock;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use crate::{
     converters::alias_generators,
-    repositories::MoveRepository,
-    tekken::{character::Character, character_move::CharacterMove},
+    move_store::MoveMap,
+    repositories::{
+        MoveRepository,
+        wavu_client::WavuClient,
+        wavu_wikitext::{clean_inline, clean_list},
+    },
+    tekken::{
+        character::Character,
+        character_move::CharacterMove,
+        provenance::{MoveSource, Provenance},
+        tracking::Tracking,
+    },
 };
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use async_trait::async_trait;
-use regex::Regex;
-use scraper::Html;
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::Deserialize;
+use tracing::warn;
 
-pub struct WavuMoveRepository;
+pub struct WavuMoveRepository {
+    api_url: String,
+    client: WavuClient,
+}
+
+impl WavuMoveRepository {
+    pub fn new(api_url: impl Into<String>, client: WavuClient) -> Self {
+        Self {
+            api_url: api_url.into(),
+            client,
+        }
+    }
+
+    /// Repository talking to a different MediaWiki API than wavu.wiki's, e.g. a mirror
+    /// or a local stand-in for tests
+    pub fn with_api_url(api_url: impl Into<String>) -> Self {
+        Self::new(api_url, WavuClient::default())
+    }
+}
+
+impl Default for WavuMoveRepository {
+    fn default() -> Self {
+        Self::with_api_url(WAVU_API_URL)
+    }
+}
 
 #[async_trait]
 impl MoveRepository for WavuMoveRepository {
     async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
-        let client = reqwest::Client::new();
-
-        let params = [
-            ("action", "cargoquery"),
-            ("tables", "Move"),
-            ("fields", &QUERY_FIELDS.join(",")),
-            ("where", &format!("id LIKE '{character}%'")),
-            ("having", ""),
-            ("order_by", "id"),
-            ("limit", "500"),
-            ("format", "json"),
+        let where_clause = move_id_where_clause(&character.move_id_prefix());
+
+        let rows = fetch_all_pages(
+            &format!("moves for {character}"),
+            PAGE_SIZE,
+            MAX_PAGES,
+            |offset| self.fetch_move_page(Some(&where_clause), offset),
+        )
+        .await?;
+
+        if rows.len() > LARGE_MOVELIST_SIZE {
+            warn!(
+                "{character} has an unusually large movelist of {} moves",
+                rows.len()
+            );
+        }
+
+        // A character whose name starts with this one's and a dash, e.g. "Jack" and
+        // "Jack-8", shares the prefix of its ids
+        let rows = rows
+            .into_iter()
+            .filter(|row| {
+                let own_move = character_of_move(&row.id) == Some(character);
+                if !own_move {
+                    warn!("Skipping move {} that isn't {character}'s", row.id);
+                }
+                own_move
+            })
+            .collect();
+
+        let mut moves = into_character_moves(character, rows);
+        let revisions = self.page_revisions(&[character]).await;
+        set_revision(&mut moves, revisions.get(&character).copied());
+
+        Ok(moves)
+    }
+
+    /// The client retries throttled and failed requests
+    fn retries_requests(&self) -> bool {
+        true
+    }
+
+    /// Loads the whole move table with a handful of paginated queries and splits it up
+    /// by the character name every move id starts with. Characters without any rows are
+    /// left out rather than given an empty movelist
+    async fn all_moves(&self) -> Option<Result<MoveMap>> {
+        let rows = match fetch_all_pages("all moves", PAGE_SIZE, BULK_MAX_PAGES, |offset| {
+            self.fetch_move_page(None, offset)
+        })
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut rows_by_character: HashMap<Character, Vec<MoveTableRow>> = HashMap::new();
+        for row in rows {
+            match character_of_move(&row.id) {
+                Some(character) => rows_by_character.entry(character).or_default().push(row),
+                None => warn!("Skipping move {} of an unknown character", row.id),
+            }
+        }
+
+        let characters = rows_by_character.keys().copied().collect::<Vec<_>>();
+        let revisions = self.page_revisions(&characters).await;
+
+        Some(Ok(rows_by_character
+            .into_iter()
+            .map(|(character, rows)| {
+                let mut moves = into_character_moves(character, rows);
+                set_revision(&mut moves, revisions.get(&character).copied());
+                (character, moves)
+            })
+            .collect()))
+    }
+
+    async fn changed_characters(&self, since: DateTime<Utc>) -> Option<Result<HashSet<Character>>> {
+        Some(self.fetch_recent_changes(since).await)
+    }
+
+    /// Every page of the move table is a character's movelist
+    async fn character_names(&self) -> Option<Result<Vec<String>>> {
+        Some(self.fetch_movelist_pages().await.map(|pages| {
+            pages
+                .iter()
+                .filter_map(|page| page.strip_suffix(MOVELIST_PAGE_SUFFIX))
+                .map(String::from)
+                .collect()
+        }))
+    }
+}
+
+impl WavuMoveRepository {
+    /// Characters whose movelist page was edited since `since`, from the wiki's recent
+    /// changes
+    async fn fetch_recent_changes(&self, since: DateTime<Utc>) -> Result<HashSet<Character>> {
+        let mut changed = HashSet::new();
+        let mut continue_params = HashMap::new();
+
+        for _ in 0..MAX_PAGES {
+            let mut params = vec![
+                ("action", "query".to_string()),
+                ("list", "recentchanges".into()),
+                ("rcend", since.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                ("rcnamespace", "0".into()),
+                ("rcprop", "title".into()),
+                ("rctype", "edit|new".into()),
+                ("rclimit", PAGE_SIZE.to_string()),
+                ("format", "json".into()),
+            ];
+            params.extend(
+                continue_params
+                    .iter()
+                    .map(|(key, value): (&String, &String)| (key.as_str(), value.clone())),
+            );
+
+            let response = self
+                .client
+                .get_json_uncached::<RecentChangesResponse>(&self.api_url, &params)
+                .await?;
+
+            changed.extend(
+                response
+                    .query
+                    .recentchanges
+                    .iter()
+                    .filter_map(|change| character_of_page(&change.title)),
+            );
+
+            match response.continue_params {
+                Some(next) => continue_params = next,
+                None => return Ok(changed),
+            }
+        }
+
+        bail!("recent changes since {since} span more than {MAX_PAGES} pages")
+    }
+
+    /// Latest revision of the movelist page of every character. The moves are usable
+    /// without it, so failing to get it is only logged
+    async fn page_revisions(&self, characters: &[Character]) -> HashMap<Character, u64> {
+        let mut revisions = HashMap::new();
+
+        for chunk in characters.chunks(MAX_TITLES) {
+            let titles = chunk
+                .iter()
+                .map(|character| movelist_page(*character))
+                .collect::<Vec<_>>();
+            let params = vec![
+                ("action", "query".to_string()),
+                ("prop", "info".into()),
+                ("titles", titles.join("|")),
+                ("formatversion", "2".into()),
+                ("format", "json".into()),
+            ];
+
+            match self
+                .client
+                .get_json::<PageInfoResponse>(&self.api_url, &params)
+                .await
+            {
+                Ok(response) => {
+                    revisions.extend(response.query.pages.into_iter().filter_map(|page| {
+                        Some((character_of_page(&page.title)?, page.lastrevid?))
+                    }))
+                }
+                Err(e) => warn!(
+                    "Failed to get the revisions of {}: {e:#}",
+                    titles.join(", ")
+                ),
+            }
+        }
+
+        revisions
+    }
+
+    /// Names of the pages with rows in the move table
+    async fn fetch_movelist_pages(&self) -> Result<Vec<String>> {
+        let params = vec![
+            ("action", "cargoquery".to_string()),
+            ("tables", "Move".into()),
+            ("fields", "_pageName=page".into()),
+            ("group_by", "_pageName".into()),
+            ("limit", PAGE_SIZE.to_string()),
+            ("format", "json".into()),
+        ];
+
+        let response = self
+            .client
+            .get_json::<PageNameQueryResponse>(&self.api_url, &params)
+            .await?;
+
+        Ok(response
+            .cargoquery
+            .into_iter()
+            .map(|entry| entry.title.page)
+            .collect())
+    }
+
+    /// Fetches a page of the move table, every move if there's no `where_clause`
+    async fn fetch_move_page(
+        &self,
+        where_clause: Option<&str>,
+        offset: usize,
+    ) -> Result<Vec<MoveTableRow>> {
+        let mut params = vec![
+            ("action", "cargoquery".to_string()),
+            ("tables", "Move".into()),
+            ("fields", QUERY_FIELDS.join(",")),
+            ("having", "".into()),
+            ("order_by", "id".into()),
+            ("limit", PAGE_SIZE.to_string()),
+            ("offset", offset.to_string()),
+            ("format", "json".into()),
         ];
+        if let Some(where_clause) = where_clause {
+            params.push(("where", where_clause.into()));
+        }
 
-        let response = client
-            .get(WAVU_API_URL)
-            .query(&params)
-            .send()
-            .await?
-            .json::<MoveTableQueryResponse>()
+        let response = self
+            .client
+            .get_json::<MoveTableQueryResponse>(&self.api_url, &params)
             .await?;
 
-        let mut character_moves = response
+        Ok(response
             .cargoquery
             .into_iter()
-            .map(|entry| entry.title.into())
-            .collect::<Vec<CharacterMove>>();
+            .map(|entry| entry.title)
+            .collect())
+    }
+}
 
-        // Add aliases to increase the chance of finding the moves people actually intend to see
-        for m in character_moves.iter_mut() {
-            let alias = alias_generators::drop_first_plus_after_letter(character, &m.id);
-            let alias2 = alias_generators::remove_commas_from_ff_notation(&alias);
+fn into_character_moves(character: Character, rows: Vec<MoveTableRow>) -> Vec<CharacterMove> {
+    let provenance = Provenance {
+        source: MoveSource::Wavu,
+        page: Some(movelist_page(character)),
+        fetched_at: Some(Utc::now()),
+        ..Default::default()
+    };
+    let mut character_moves = rows
+        .into_iter()
+        .map(|row| CharacterMove {
+            provenance: provenance.clone(),
+            ..CharacterMove::from(row)
+        })
+        .collect::<Vec<CharacterMove>>();
 
-            if alias2 != m.id {
-                m.alias.push(alias2.into());
-            }
+    // Add aliases to increase the chance of finding the moves people actually intend to see
+    for m in character_moves.iter_mut() {
+        let alias = alias_generators::drop_first_plus_after_letter(character, &m.id);
+        let alias2 = alias_generators::remove_commas_from_ff_notation(&alias);
+
+        if alias2 != m.id {
+            m.alias.push(alias2.into());
         }
+    }
+
+    character_moves
+}
+
+/// Move ids start with the character's name followed by a dash, e.g. "Jack-8-1,2".
+/// The longest matching name wins so a character can't steal another one's moves
+fn character_of_move(id: &str) -> Option<Character> {
+    Character::iter()
+        .filter(|character| {
+            id.strip_prefix(&character.wavu_name())
+                .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|character| character.wavu_name().len())
+}
+
+/// Cargo `where` clause matching the moves whose id starts with `prefix`
+pub fn move_id_where_clause(prefix: &str) -> String {
+    format!("id LIKE '{}%'", prefix.replace('\'', "''"))
+}
+
+fn set_revision(moves: &mut [CharacterMove], revision: Option<u64>) {
+    for m in moves {
+        m.provenance.revision = revision;
+    }
+}
+
+/// Moves are kept on one page per character, e.g. "Jack-8 movelist"
+fn movelist_page(character: Character) -> String {
+    format!("{}{MOVELIST_PAGE_SUFFIX}", character.wavu_name())
+}
+
+fn character_of_page(title: &str) -> Option<Character> {
+    title
+        .strip_suffix(MOVELIST_PAGE_SUFFIX)
+        .and_then(Character::from_name)
+}
 
-        Ok(character_moves)
+/// Keeps requesting pages of `page_size` rows until a page comes back short. Fails after
+/// `max_pages` so a misbehaving API can't keep us looping forever, and so a truncated
+/// result is never mistaken for the whole one
+async fn fetch_all_pages<F, Fut>(
+    what: &str,
+    page_size: usize,
+    max_pages: usize,
+    mut fetch_page: F,
+) -> Result<Vec<MoveTableRow>>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<Vec<MoveTableRow>>>,
+{
+    let mut rows = Vec::new();
+
+    for page in 0..max_pages {
+        let page_rows = fetch_page(page * page_size).await?;
+        let is_last_page = page_rows.len() < page_size;
+        rows.extend(page_rows);
+
+        if is_last_page {
+            return Ok(rows);
+        }
     }
+
+    bail!(
+        "stopped fetching {what} after {max_pages} pages, the result is truncated at {} moves",
+        rows.len()
+    )
 }
 
-const WAVU_API_URL: &str = "https://wavu.wiki/w/api.php";
+pub const WAVU_API_URL: &str = "https://wavu.wiki/w/api.php";
+
+const MOVELIST_PAGE_SUFFIX: &str = " movelist";
+
+/// Highest `limit` the cargoquery API accepts
+const PAGE_SIZE: usize = 500;
+
+const MAX_PAGES: usize = 10;
+
+/// Most titles a single page info query accepts
+const MAX_TITLES: usize = 50;
+
+/// The whole move table is a few thousand rows
+const BULK_MAX_PAGES: usize = 50;
+
+/// Movelists are usually a couple hundred moves, anything past this is suspicious
+const LARGE_MOVELIST_SIZE: usize = 1000;
 
 const QUERY_FIELDS: [&str; 20] = [
     "id",
@@ -83,6 +416,58 @@ const QUERY_FIELDS: [&str; 20] = [
     "notes",
 ];
 
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct RecentChangesResponse {
+    /// Parameters to send along to get the next page, missing on the last page
+    #[serde(rename = "continue")]
+    continue_params: Option<HashMap<String, String>>,
+    query: RecentChangesQuery,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct RecentChangesQuery {
+    recentchanges: Vec<RecentChange>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct RecentChange {
+    title: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct PageInfoResponse {
+    query: PageInfoQuery,
+}
+
+#[derive(Debug, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+struct PageInfoQuery {
+    pages: Vec<PageInfo>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct PageInfo {
+    title: String,
+    /// Missing if the page doesn't exist
+    lastrevid: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct PageNameQueryResponse {
+    cargoquery: Vec<PageNameEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct PageNameEntry {
+    title: PageNameRow,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct PageNameRow {
+    page: String,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Deserialize)]
 struct MoveTableQueryResponse {
     cargoquery: Vec<MoveTableResponseEntry>,
@@ -93,7 +478,7 @@ struct MoveTableResponseEntry {
     title: MoveTableRow,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct MoveTableRow {
     id: String,
@@ -118,104 +503,443 @@ struct MoveTableRow {
     notes: Option<String>,
 }
 
-static LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\[.*?\|(.*?)\]\]").unwrap());
+impl From<MoveTableRow> for CharacterMove {
+    fn from(row: MoveTableRow) -> Self {
+        let inline = |value: Option<String>| value.map(|s| clean_inline(&s));
+        let list = |value: Option<String>| value.map(|s| clean_list(&s)).unwrap_or_default();
 
-impl MoveTableRow {
-    fn decode_bullet_list(bullet_list_html: &Option<impl AsRef<str>>) -> Vec<String> {
-        let Some(html) = bullet_list_html else {
-            return Vec::new();
-        };
+        CharacterMove {
+            id: clean_inline(&row.id),
+            name: inline(row.name),
+            input: inline(row.input),
+            alias: list(row.alias),
+            alt: list(row.alt),
+            parent: inline(row.parent),
+            target: inline(row.target),
+            damage: inline(row.damage),
+            reach: inline(row.reach),
+            tracking: Tracking::new(
+                inline(row.tracks_left).as_deref(),
+                inline(row.tracks_right).as_deref(),
+            ),
+            startup_frames: inline(row.startup),
+            recovery_frames: inline(row.recv),
+            total_frames: inline(row.tot),
+            crush: inline(row.crush),
+            on_block: inline(row.block),
+            on_hit: inline(row.hit),
+            on_counter_hit: inline(row.ch),
+            notes: list(row.notes),
+            overridden: BTreeSet::new(),
+            provenance: Provenance::default(),
+        }
+    }
+}
 
-        let decoded = html_escape::decode_html_entities(html);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::wavu_stand_in::{FIXTURE_DIR, WavuStandIn};
+    use rstest::rstest;
+    use wiremock::ResponseTemplate;
 
-        Html::parse_fragment(&decoded)
-            .root_element()
-            .text()
-            .flat_map(|s| s.lines())
-            .map(MoveTableRow::remove_links)
-            .map(|s| s.replace("* ", ""))
-            .filter(|s| !s.trim().is_empty())
+    fn fixture_page(name: &str) -> Vec<MoveTableRow> {
+        let path = format!("{FIXTURE_DIR}/{name}");
+        let json = std::fs::read_to_string(&path).unwrap();
+        serde_json::from_str::<MoveTableQueryResponse>(&json)
+            .unwrap()
+            .cargoquery
+            .into_iter()
+            .map(|entry| entry.title)
             .collect()
     }
 
-    /// Justframe input is shown as "#" on wavu wiki, but in the API you get
-    /// "${justFrame}" for some reason
-    ///
-    /// Returns `Some` with the fixed string if the justframe pattern was replaced,
-    /// `None` otherwise
-    fn fix_justframe_notation(s: &str) -> Option<String> {
-        let justframe_pattern = "${justFrame}";
-        if s.contains(justframe_pattern) {
-            return Some(s.replace(justframe_pattern, "#"));
-        }
-        None
+    #[tokio::test]
+    async fn test_fetch_all_pages_follows_offsets_until_short_page() {
+        let pages = [
+            fixture_page("paul_page_1.json"),
+            fixture_page("paul_page_2.json"),
+            fixture_page("paul_page_3.json"),
+        ];
+        let mut offsets = Vec::new();
+
+        let rows = fetch_all_pages("moves for Paul", 2, 10, |offset| {
+            offsets.push(offset);
+            let page = pages[offset / 2].clone();
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(offsets, vec![0, 2, 4]);
+        let ids = rows.iter().map(|r| r.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec!["Paul-1", "Paul-1,2", "Paul-2", "Paul-b+1", "Paul-qcf+2"]
+        );
     }
 
-    /// Frame data fields sometimes contain links such as "[[Eddy combos#Staples|+31a(+24)]]",
-    /// this function removes those kinds of links so we get "+31a(+24)" instead
-    fn remove_links(s: &str) -> String {
-        let re = &*LINK_REGEX;
-        re.replace(s, "$1").into()
+    #[tokio::test]
+    async fn test_fetch_all_pages_requests_one_more_page_after_full_page() {
+        let full_page = fixture_page("paul_page_1.json");
+        let mut offsets = Vec::new();
+
+        let rows = fetch_all_pages("moves for Paul", 2, 10, |offset| {
+            offsets.push(offset);
+            let page = match offset {
+                0 => full_page.clone(),
+                _ => Vec::new(),
+            };
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(offsets, vec![0, 2]);
+        assert_eq!(rows.len(), 2);
     }
-}
 
-impl From<MoveTableRow> for CharacterMove {
-    fn from(row: MoveTableRow) -> Self {
-        let fixed_id = MoveTableRow::fix_justframe_notation(&row.id);
+    #[tokio::test]
+    async fn test_fetch_all_pages_fails_at_max_pages() {
+        let full_page = fixture_page("paul_page_1.json");
+        let mut requests = 0;
 
-        CharacterMove {
-            id: fixed_id.unwrap_or(row.id),
-            name: row.name,
-            input: row.input,
-            alias: MoveTableRow::decode_bullet_list(&row.alias),
-            alt: MoveTableRow::decode_bullet_list(&row.alt),
-            parent: row.parent,
-            target: row.target,
-            damage: row.damage,
-            reach: row.reach,
-            startup_frames: row.startup,
-            recovery_frames: row.recv,
-            total_frames: row.tot,
-            crush: row.crush,
-            on_block: row.block.map(|s| MoveTableRow::remove_links(&s)),
-            on_hit: row.hit.map(|s| MoveTableRow::remove_links(&s)),
-            on_counter_hit: row.ch.map(|s| MoveTableRow::remove_links(&s)),
-            notes: MoveTableRow::decode_bullet_list(&row.notes),
+        let result = fetch_all_pages("moves for Paul", 2, 3, |_| {
+            requests += 1;
+            let page = full_page.clone();
+            async move { Ok(page) }
+        })
+        .await;
+
+        assert_eq!(requests, 3);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_pages_propagates_errors() {
+        let result = fetch_all_pages("moves for Paul", 2, 10, |offset| async move {
+            match offset {
+                0 => Ok(fixture_page("paul_page_1.json")),
+                _ => Err(anyhow::anyhow!("wavu is down")),
+            }
+        })
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_from_stand_in() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+        let repository = wavu.repository();
+
+        let moves = repository.character_moves(Character::Kazuya).await.unwrap();
+
+        assert_eq!(moves.len(), 5);
+        let ewgf = &moves[3];
+        assert_eq!(ewgf.id, "Kazuya-f,n,d,df#2");
+        assert_eq!(ewgf.name.as_deref(), Some("Electric Wind God Fist"));
+        assert_eq!(ewgf.on_hit.as_deref(), Some("+21a (+11)"));
+        assert_eq!(ewgf.alias, vec!["EWGF"]);
+        assert_eq!(ewgf.notes, vec!["Heat Engager", "Balcony Break"]);
+        assert_eq!(moves[4].alias, vec!["df1"]);
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_provenance() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+        wavu.serve_page_info("page_info.json").await;
+        let before = Utc::now();
+
+        let moves = wavu
+            .repository()
+            .character_moves(Character::Kazuya)
+            .await
+            .unwrap();
+
+        let provenance = &moves[0].provenance;
+        assert_eq!(provenance.source, MoveSource::Wavu);
+        assert_eq!(provenance.page.as_deref(), Some("Kazuya movelist"));
+        assert_eq!(provenance.revision, Some(48213));
+        assert!(provenance.fetched_at.is_some_and(|at| at >= before));
+        assert_eq!(provenance.overridden_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_without_revisions() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+        wavu.serve_page_info_response(ResponseTemplate::new(404))
+            .await;
+
+        let moves = wavu
+            .repository()
+            .character_moves(Character::Kazuya)
+            .await
+            .unwrap();
+
+        assert_eq!(moves.len(), 5);
+        assert_eq!(moves[0].provenance.revision, None);
+        assert_eq!(moves[0].provenance.source, MoveSource::Wavu);
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_removes_links_from_stand_in_data() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_fixture(Character::Paul, "paul.json").await;
+        let repository = wavu.repository();
+
+        let moves = repository.character_moves(Character::Paul).await.unwrap();
+
+        let phoenix_smasher = moves.iter().find(|m| m.id == "Paul-qcf+2").unwrap();
+        assert_eq!(phoenix_smasher.on_hit.as_deref(), Some("+31a(+21)"));
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_tracking() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_fixture(Character::Bryan, "bryan.json").await;
+        let repository = wavu.repository();
+
+        let moves = repository.character_moves(Character::Bryan).await.unwrap();
+
+        assert_eq!(moves[0].tracking, Tracking::new(Some("n"), Some("y")));
+        assert!(!moves[1].tracking.is_known());
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_empty_result() {
+        let wavu = WavuStandIn::start().await;
+        let repository = wavu.repository();
+
+        let moves = repository.character_moves(Character::Leo).await.unwrap();
+
+        assert!(moves.is_empty());
+    }
+
+    #[rstest]
+    #[case(ResponseTemplate::new(500))]
+    #[case(ResponseTemplate::new(503).set_body_string("Service Unavailable"))]
+    #[case(ResponseTemplate::new(200).set_body_raw(r#"{"cargoquery": [{"title": "#, "application/json"))]
+    #[case(ResponseTemplate::new(200).set_body_raw(r#"{"error": {"code": "internal_api_error"}}"#, "application/json"))]
+    #[tokio::test]
+    async fn test_character_moves_bad_responses(#[case] response: ResponseTemplate) {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_response(Character::Lee, response).await;
+        let repository = wavu.repository();
+
+        let result = repository.character_moves(Character::Lee).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_all_moves_from_stand_in() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json", "bryan.json"])
+            .await;
+        let repository = wavu.repository();
+
+        wavu.serve_page_info("page_info.json").await;
+
+        let moves = repository.all_moves().await.unwrap().unwrap();
+
+        // The move table, then the revisions of every movelist page
+        assert_eq!(wavu.request_count().await, 2);
+        assert_eq!(moves.len(), 3);
+        assert_eq!(moves[&Character::Kazuya].len(), 5);
+        assert_eq!(moves[&Character::Kazuya][3].alias, vec!["EWGF"]);
+        assert_eq!(moves[&Character::Paul].len(), 5);
+        assert_eq!(moves[&Character::Bryan].len(), 2);
+        assert!(!moves.contains_key(&Character::Leo));
+        assert_eq!(moves[&Character::Paul][0].provenance.revision, Some(48207));
+        assert_eq!(moves[&Character::Bryan][0].provenance.revision, None);
+    }
+
+    #[tokio::test]
+    async fn test_all_moves_follows_pages() {
+        let wavu = WavuStandIn::start().await;
+        let full_page = (0..PAGE_SIZE)
+            .map(|i| format!(r#"{{"title": {{"id": "Jack-8-{i}"}}}}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        wavu.serve_all_moves_response(
+            0,
+            ResponseTemplate::new(200).set_body_raw(
+                format!(r#"{{"cargoquery": [{full_page}]}}"#),
+                "application/json",
+            ),
+        )
+        .await;
+        wavu.serve_all_moves(PAGE_SIZE, &["kazuya.json"]).await;
+        let repository = wavu.repository();
+
+        let moves = repository.all_moves().await.unwrap().unwrap();
+
+        assert_eq!(wavu.request_count().await, 3);
+        assert_eq!(moves[&Character::Jack8].len(), PAGE_SIZE);
+        assert_eq!(moves[&Character::Kazuya].len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_all_moves_truncated() {
+        let wavu = WavuStandIn::start().await;
+        let full_page = |page: usize| {
+            let rows = (0..PAGE_SIZE)
+                .map(|i| {
+                    format!(
+                        r#"{{"title": {{"id": "Jack-8-{}"}}}}"#,
+                        page * PAGE_SIZE + i
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            ResponseTemplate::new(200)
+                .set_body_raw(format!(r#"{{"cargoquery": [{rows}]}}"#), "application/json")
+        };
+        for page in 0..BULK_MAX_PAGES {
+            wavu.serve_all_moves_response(page * PAGE_SIZE, full_page(page))
+                .await;
         }
+        // Rows past the last page the bulk query asks for
+        wavu.serve_all_moves(BULK_MAX_PAGES * PAGE_SIZE, &["kazuya.json"])
+            .await;
+        let repository = wavu.repository();
+
+        let result = repository.all_moves().await.unwrap();
+
+        assert_eq!(wavu.request_count().await, BULK_MAX_PAGES);
+        assert!(result.is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rstest::rstest;
+    #[tokio::test]
+    async fn test_all_moves_bad_response() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves_response(0, ResponseTemplate::new(503))
+            .await;
+        let repository = wavu.repository();
+
+        let result = repository.all_moves().await.unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_changed_characters_from_stand_in() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_recent_changes("recent_changes.json").await;
+        let since = "2025-10-12T18:30:00Z".parse().unwrap();
+
+        let changed = wavu
+            .repository()
+            .changed_characters(since)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(changed, HashSet::from([Character::Paul, Character::Kazuya]));
+    }
+
+    #[tokio::test]
+    async fn test_changed_characters_follows_continuation() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_recent_changes("recent_changes_page_1.json")
+            .await;
+        wavu.serve_recent_changes_response(
+            Some("20251012180000|4242"),
+            ResponseTemplate::new(200).set_body_raw(
+                std::fs::read_to_string(format!("{FIXTURE_DIR}/recent_changes.json")).unwrap(),
+                "application/json",
+            ),
+        )
+        .await;
+
+        let changed = wavu
+            .repository()
+            .changed_characters(Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(wavu.request_count().await, 2);
+        assert_eq!(
+            changed,
+            HashSet::from([Character::Bryan, Character::Paul, Character::Kazuya])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_characters_bad_response() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_recent_changes_response(None, ResponseTemplate::new(500))
+            .await;
 
-    #[test]
-    fn test_replace_justframe_notation() {
-        let fixed = MoveTableRow::fix_justframe_notation("Kazuya-f,n,d,df${justFrame}2");
-        assert_eq!(fixed.unwrap(), "Kazuya-f,n,d,df#2");
+        let result = wavu.repository().changed_characters(Utc::now()).await;
+
+        assert!(result.unwrap().is_err());
     }
 
-    #[test]
-    fn test_replace_justframe_notation_no_justframe_pattern_match() {
-        let fixed = MoveTableRow::fix_justframe_notation("Kazuya-f,n,d,df2");
-        assert!(fixed.is_none());
+    #[tokio::test]
+    async fn test_character_names_from_stand_in() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_movelist_pages("movelist_pages.json").await;
+
+        let names = wavu.repository().character_names().await.unwrap().unwrap();
+
+        assert_eq!(names, vec!["Armor King", "Jack-8", "Paul", "Some Guest"]);
+    }
+
+    #[rstest]
+    #[case("Jin-", "id LIKE 'Jin-%'")]
+    #[case("Jinpachi-", "id LIKE 'Jinpachi-%'")]
+    #[case("Jack-8-", "id LIKE 'Jack-8-%'")]
+    #[case("Ling's-", "id LIKE 'Ling''s-%'")]
+    fn test_move_id_where_clause(#[case] prefix: &str, #[case] expected: &str) {
+        assert_eq!(move_id_where_clause(prefix), expected);
+    }
+
+    #[tokio::test]
+    async fn test_character_moves_skips_moves_of_other_characters() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_response(
+            Character::Lee,
+            ResponseTemplate::new(200).set_body_raw(
+                r#"{"cargoquery": [{"title": {"id": "Lee-b+4"}}, {"title": {"id": "Leeroy-1"}}]}"#,
+                "application/json",
+            ),
+        )
+        .await;
+
+        let moves = wavu
+            .repository()
+            .character_moves(Character::Lee)
+            .await
+            .unwrap();
+
+        let ids = moves.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["Lee-b+4"]);
+    }
+
+    #[rstest]
+    #[case("Paul movelist", Some(Character::Paul))]
+    #[case("Jack-8 movelist", Some(Character::Jack8))]
+    #[case("Armor King movelist", Some(Character::ArmorKing))]
+    #[case("Paul combos", None)]
+    #[case("Main Page", None)]
+    fn test_character_of_page(#[case] title: &str, #[case] expected: Option<Character>) {
+        assert_eq!(character_of_page(title), expected);
     }
 
     #[rstest]
-    #[case("[[Eddy combos#Staples|+31a(+24)]]", "+31a(+24)")]
-    #[case("[[Asuka_combos#Staples|+22a (+12)]]", "+22a (+12)")]
-    #[case("+23a (+13)", "+23a (+13)")]
-    #[case("+2", "+2")]
-    #[case("", "")]
-    #[case("[[Asuka_combos#Staples|]]", "")]
-    #[case("[[Paul_movelist#Paul-H.CS.2|H.CS.2]] with Heat", "H.CS.2 with Heat")]
-    #[case(
-        "Hi [[Paul_movelist#Paul-H.CS.2|H.CS.2]] with Heat",
-        "Hi H.CS.2 with Heat"
-    )]
-    fn test_remove_links(#[case] with_link: &str, #[case] without_link: &str) {
-        let s = MoveTableRow::remove_links(with_link);
-        assert_eq!(without_link, s)
+    #[case("Paul-qcf+2", Some(Character::Paul))]
+    #[case("Jack-8-1,2", Some(Character::Jack8))]
+    #[case("Armor King-df+1", Some(Character::ArmorKing))]
+    #[case("Lee-b+4", Some(Character::Lee))]
+    #[case("Leeroy-1", None)]
+    #[case("Heihachi Mishima-1", None)]
+    fn test_character_of_move(#[case] id: &str, #[case] expected: Option<Character>) {
+        assert_eq!(character_of_move(id), expected);
     }
 }
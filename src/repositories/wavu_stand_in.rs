@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path, query_param, query_param_is_missing},
+};
+
+use crate::{
+    repositories::{
+        wavu_client::{WavuClient, WavuClientConfig},
+        wavu_move_repository::{WavuMoveRepository, move_id_where_clause},
+    },
+    tekken::character::Character,
+};
+
+pub const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/wavu");
+
+const API_PATH: &str = "/w/api.php";
+
+/// Local stand-in for the Wavu MediaWiki API, serving recorded cargoquery responses so
+/// the whole fetch path can be tested without network access
+pub struct WavuStandIn {
+    server: MockServer,
+}
+
+impl WavuStandIn {
+    /// Starts a stand-in that answers every query with an empty result until
+    /// characters are given responses
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .respond_with(cargoquery_json("[]"))
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+
+        Self { server }
+    }
+
+    pub fn api_url(&self) -> String {
+        format!("{}{API_PATH}", self.server.uri())
+    }
+
+    /// Repository talking to the stand-in that retries without waiting, so tests of
+    /// failing responses stay fast
+    pub fn repository(&self) -> WavuMoveRepository {
+        let client = WavuClient::new(WavuClientConfig {
+            retry_backoff: Duration::from_millis(1),
+            ..Default::default()
+        });
+        WavuMoveRepository::new(self.api_url(), client)
+    }
+
+    /// Serves `tests/fixtures/wavu/<fixture>` as the first page of `character`'s moves
+    pub async fn serve_fixture(&self, character: Character, fixture: &str) {
+        self.serve_response(character, fixture_response(fixture))
+            .await;
+    }
+
+    pub async fn serve_response(&self, character: Character, response: ResponseTemplate) {
+        Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .and(query_param("action", "cargoquery"))
+            .and(query_param(
+                "where",
+                move_id_where_clause(&character.move_id_prefix()),
+            ))
+            .and(query_param("offset", "0"))
+            .respond_with(response)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serves the moves of several fixtures as the page of the bulk query for every
+    /// character starting at `offset`
+    pub async fn serve_all_moves(&self, offset: usize, fixtures: &[&str]) {
+        let rows = fixtures
+            .iter()
+            .flat_map(|fixture| {
+                let body = std::fs::read_to_string(format!("{FIXTURE_DIR}/{fixture}")).unwrap();
+                let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+                json["cargoquery"].as_array().unwrap().clone()
+            })
+            .collect::<Vec<_>>();
+
+        self.serve_all_moves_response(
+            offset,
+            cargoquery_json(&serde_json::Value::from(rows).to_string()),
+        )
+        .await;
+    }
+
+    pub async fn serve_all_moves_response(&self, offset: usize, response: ResponseTemplate) {
+        Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .and(query_param("action", "cargoquery"))
+            .and(query_param_is_missing("where"))
+            .and(query_param("offset", offset.to_string()))
+            .respond_with(response)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serves `tests/fixtures/wavu/<fixture>` as the first page of recent changes
+    pub async fn serve_recent_changes(&self, fixture: &str) {
+        self.serve_recent_changes_response(None, fixture_response(fixture))
+            .await;
+    }
+
+    /// Serves the page of recent changes requested with `rccontinue`, or the first
+    /// page if it's `None`
+    pub async fn serve_recent_changes_response(
+        &self,
+        rccontinue: Option<&str>,
+        response: ResponseTemplate,
+    ) {
+        let mock = Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .and(query_param("action", "query"))
+            .and(query_param("list", "recentchanges"));
+        let mock = match rccontinue {
+            Some(rccontinue) => mock.and(query_param("rccontinue", rccontinue)),
+            None => mock.and(query_param_is_missing("rccontinue")),
+        };
+
+        mock.respond_with(response).mount(&self.server).await;
+    }
+
+    /// Serves `tests/fixtures/wavu/<fixture>` as the pages of the move table
+    pub async fn serve_movelist_pages(&self, fixture: &str) {
+        Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .and(query_param("action", "cargoquery"))
+            .and(query_param("group_by", "_pageName"))
+            .respond_with(fixture_response(fixture))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serves `tests/fixtures/wavu/<fixture>` as the page info of every movelist page
+    pub async fn serve_page_info(&self, fixture: &str) {
+        self.serve_page_info_response(fixture_response(fixture))
+            .await;
+    }
+
+    pub async fn serve_page_info_response(&self, response: ResponseTemplate) {
+        Mock::given(method("GET"))
+            .and(path(API_PATH))
+            .and(query_param("action", "query"))
+            .and(query_param("prop", "info"))
+            .respond_with(response)
+            .mount(&self.server)
+            .await;
+    }
+
+    pub async fn request_count(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .map(|requests| requests.len())
+            .unwrap_or_default()
+    }
+}
+
+fn fixture_response(fixture: &str) -> ResponseTemplate {
+    let body = std::fs::read_to_string(format!("{FIXTURE_DIR}/{fixture}")).unwrap();
+    ResponseTemplate::new(200).set_body_raw(body, "application/json")
+}
+
+fn cargoquery_json(rows: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .set_body_raw(format!(r#"{{"cargoquery":{rows}}}"#), "application/json")
+}
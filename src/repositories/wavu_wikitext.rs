@@ -0,0 +1,188 @@
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+use tracing::warn;
+
+/// Stand-ins for characters that would break the wikitext they're in, with what the wiki
+/// shows for them. Move ids are used as link anchors, which can't hold a `#`, and template
+/// arguments can't hold a `|` or `=`. Unknown `${...}` placeholders are left as they are so
+/// lint reports them
+const PLACEHOLDERS: [(&str, &str); 3] = [
+    ("${justFrame}", "#"),
+    // MediaWiki's magic words for the characters that separate template arguments
+    ("{{!}}", "|"),
+    ("{{=}}", "="),
+];
+
+static PLACEHOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{\w+\}|\{\{[!=]\}\}").unwrap());
+/// `{{name|arg|key=value}}`, without nested templates
+static TEMPLATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{([^{}|]*)((?:\|[^{}]*)?)\}\}").unwrap());
+/// `[[page]]` or `[[page|text]]`
+static LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\[([^\[\]|]*)(?:\|([^\[\]]*))?\]\]").unwrap());
+/// `[https://example.com text]`
+static EXTERNAL_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(?:https?:)?//[^\s\]]+(?: ([^\]]*))?\]").unwrap());
+static BOLD_ITALIC_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"'{2,}").unwrap());
+static LINE_BREAK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"</?[a-zA-Z][^<>]*>").unwrap());
+
+/// Turns a single line value such as an id, name or frame data into the plain text the
+/// wiki shows for it. Line breaks become spaces
+pub fn clean_inline(s: &str) -> String {
+    clean(s).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Turns a bullet list such as notes or aliases into its items as plain text
+pub fn clean_list(s: &str) -> Vec<String> {
+    clean(s)
+        .lines()
+        .map(|line| {
+            line.trim_start()
+                .trim_start_matches('*')
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Plain text with the line breaks of the wikitext and HTML kept
+fn clean(s: &str) -> String {
+    // The cargo API escapes HTML, some fields are escaped twice
+    let decoded = html_escape::decode_html_entities(s);
+    let decoded = html_escape::decode_html_entities(&decoded);
+
+    let text = LINE_BREAK_REGEX.replace_all(&decoded, "\n");
+    let text = TAG_REGEX.replace_all(&text, "");
+    let text = PLACEHOLDER_REGEX.replace_all(&text, |caps: &Captures| {
+        match PLACEHOLDERS
+            .iter()
+            .find(|(placeholder, _)| *placeholder == &caps[0])
+        {
+            Some((_, replacement)) => replacement.to_string(),
+            None => {
+                warn!("Unknown placeholder {}", &caps[0]);
+                caps[0].to_string()
+            }
+        }
+    });
+    let text = replace_templates(&text);
+    let text = LINK_REGEX.replace_all(&text, |caps: &Captures| match caps.get(2) {
+        Some(label) => label.as_str().to_string(),
+        None => caps[1].trim_start_matches(':').to_string(),
+    });
+    let text = EXTERNAL_LINK_REGEX.replace_all(&text, |caps: &Captures| {
+        caps.get(1)
+            .map(|label| label.as_str().to_string())
+            .unwrap_or_default()
+    });
+    let text = BOLD_ITALIC_REGEX.replace_all(&text, "");
+
+    text.into_owned()
+}
+
+/// Templates are replaced by their positional arguments, or their name if they have none.
+/// Innermost templates are replaced first so nested ones work too
+fn replace_templates(s: &str) -> String {
+    let mut text = s.to_string();
+    while TEMPLATE_REGEX.is_match(&text) {
+        text = TEMPLATE_REGEX
+            .replace_all(&text, |caps: &Captures| {
+                let arguments = caps[2]
+                    .split('|')
+                    .skip(1)
+                    .map(str::trim)
+                    .filter(|argument| !argument.is_empty() && !argument.contains('='))
+                    .collect::<Vec<_>>();
+                if arguments.is_empty() {
+                    caps[1].trim().to_string()
+                } else {
+                    arguments.join(" ")
+                }
+            })
+            .into_owned();
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    // Plain frame data is left alone
+    #[case::startup("i10", "i10")]
+    #[case::startup("i14~15", "i14~15")]
+    #[case::block("+1", "+1")]
+    #[case::hit("+21a (+11)", "+21a (+11)")]
+    #[case::empty("", "")]
+    // Just frame inputs in ids
+    #[case::id("Kazuya-f,n,d,df${justFrame}2", "Kazuya-f,n,d,df#2")]
+    #[case::id("Heihachi-f,n,d,df${justFrame}2", "Heihachi-f,n,d,df#2")]
+    #[case::id("Kazuya-f,n,d,df${justFrame}2,1", "Kazuya-f,n,d,df#2,1")]
+    #[case::input("f,n,d,df#2", "f,n,d,df#2")]
+    #[case::id("Lee-b+3${heat}", "Lee-b+3${heat}")]
+    // Magic words standing in for template argument separators
+    #[case::input("1{{!}}2", "1|2")]
+    #[case::notes("Range {{=}} 2.0", "Range = 2.0")]
+    // Links to combos and other moves
+    #[case::hit("[[Paul combos#Staples|+31a(+21)]]", "+31a(+21)")]
+    #[case::hit("[[Eddy combos#Staples|+31a(+24)]]", "+31a(+24)")]
+    #[case::hit("[[Asuka_combos#Staples|+22a (+12)]]", "+22a (+12)")]
+    #[case::hit("[[Asuka_combos#Staples|]]", "")]
+    #[case::notes("[[Paul_movelist#Paul-H.CS.2|H.CS.2]] with Heat", "H.CS.2 with Heat")]
+    #[case::notes(
+        "Cancel to [[Paul_movelist#Paul-H.CS.2|H.CS.2]] with Heat",
+        "Cancel to H.CS.2 with Heat"
+    )]
+    #[case::hit(
+        "[[Paul combos#Staples|+31a(+21)]] or [[Paul combos#Heat|+35a]]",
+        "+31a(+21) or +35a"
+    )]
+    #[case::notes("Transitions to [[Sway]]", "Transitions to Sway")]
+    #[case::notes("[[:Category:Paul]]", "Category:Paul")]
+    #[case::notes("[https://wavu.wiki/t/Paul Wavu]", "Wavu")]
+    // Templates are replaced by their arguments
+    #[case::notes("{{Heat}} Engager", "Heat Engager")]
+    #[case::notes("{{Sub|Balcony Break}}", "Balcony Break")]
+    #[case::notes("{{Sub|{{Heat}} Engager}}", "Heat Engager")]
+    // Bold and italic
+    #[case::notes("'''Heat Engager'''", "Heat Engager")]
+    #[case::notes("''Power crush''", "Power crush")]
+    #[case::name("'''''Tornado'''''", "Tornado")]
+    // HTML
+    #[case::startup("i15~16<br>i18", "i15~16 i18")]
+    #[case::startup("i15~16<br />i18", "i15~16 i18")]
+    #[case::block("<span class=\"hidden\">+5</span>", "+5")]
+    #[case::name("Phoenix&nbsp;Smasher", "Phoenix Smasher")]
+    #[case::name("Ling&#39;s Kick", "Ling's Kick")]
+    #[case::name("&lt;b&gt;Jab&lt;/b&gt;", "Jab")]
+    #[case::notes("-1 &lt; x", "-1 < x")]
+    fn test_clean_inline(#[case] wikitext: &str, #[case] expected: &str) {
+        assert_eq!(clean_inline(wikitext), expected);
+    }
+
+    #[rstest]
+    #[case("", vec![])]
+    #[case("* EWGF", vec!["EWGF"])]
+    #[case("* Heat Engager\n* Balcony Break", vec!["Heat Engager", "Balcony Break"])]
+    #[case(
+        "&lt;div class=&quot;plainlist&quot;&gt;\n* [[Heat]] Engager\n* Balcony Break\n&lt;/div&gt;",
+        vec!["Heat Engager", "Balcony Break"]
+    )]
+    #[case("Heat Engager<br>Balcony Break", vec!["Heat Engager", "Balcony Break"])]
+    #[case(
+        "* '''Tornado'''\n*\n* Combo from [[Paul combos#Staples|CH]]",
+        vec!["Tornado", "Combo from CH"]
+    )]
+    fn test_clean_list(#[case] wikitext: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(clean_list(wikitext), expected);
+    }
+}
@@ -1,57 +1,284 @@
+use std::sync::Arc;
+
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 
 use crate::{
-    matchers::{CharacterMoveMatch, MoveMatcher},
-    move_store::MoveStore,
+    matchers::{CharacterMoveMatch, MoveMatcher, MoveQuery},
+    move_store::{
+        MoveStore, MoveStoreConfig,
+        history::{MoveRevision, MoveVersion},
+        index::MoveSuggestion,
+        lint::LintReport,
+    },
     repositories::MoveRepository,
-    tekken::character::Character,
+    tekken::{
+        character::Character,
+        character_move::CharacterMove,
+        crush::CrushKind,
+        frames::FrameField,
+        hit_level::{HitLevel, HitLevels},
+        tracking::SidestepDirection,
+    },
 };
 
 pub struct FrameService<R: MoveRepository, M: MoveMatcher> {
-    store: MoveStore<R>,
+    store: Arc<MoveStore<R>>,
     matcher: M,
 }
 
 impl<R: MoveRepository, M: MoveMatcher> FrameService<R, M> {
-    pub async fn try_new(move_repository: R, matcher: M) -> Result<Self> {
-        let move_store = MoveStore::try_new(move_repository).await?;
+    pub async fn try_new(
+        move_repository: R,
+        matcher: M,
+        store_config: MoveStoreConfig,
+    ) -> Result<Self> {
+        let move_store = MoveStore::try_new(move_repository, store_config).await?;
         Ok(Self {
-            store: move_store,
+            store: Arc::new(move_store),
             matcher,
         })
     }
 
+    /// Shared handle to the move store, e.g. for refreshing it in the background
+    pub fn store(&self) -> Arc<MoveStore<R>> {
+        self.store.clone()
+    }
+
+    /// Whether there is any data for `character`, there might not be if fetching
+    /// their moves failed
+    pub fn is_character_available(&self, character: Character) -> bool {
+        !self.store.unavailable_characters().contains(&character)
+    }
+
+    /// Returns when the data was fetched if the service is running on a snapshot
+    /// instead of live data
+    pub fn stale_data_since(&self) -> Option<DateTime<Utc>> {
+        self.store.is_stale().then(|| self.store.fetched_at())
+    }
+
+    /// Problems in the current data, such as missing startup frames or leftover wiki markup
+    pub fn lint(&self) -> LintReport {
+        self.store.lint()
+    }
+
+    /// Problems in the current data of one character, `None` if there's no data for them
+    pub fn lint_character(&self, character: Character) -> Option<LintReport> {
+        self.store.lint_character(character)
+    }
+
+    /// Moves of `character` that are known not to track sidesteps in `direction`
+    pub fn moves_weak_to_sidestep(
+        &self,
+        character: Character,
+        direction: SidestepDirection,
+    ) -> Option<Vec<CharacterMove>> {
+        let moves = self.store.moves(character)?;
+
+        Some(
+            moves
+                .into_iter()
+                .filter(|m| m.tracking.is_weak_to_sidestep(direction))
+                .collect(),
+        )
+    }
+
+    /// Moves of `character` whose hits have exactly the given hit levels, e.g. all
+    /// mid-low strings
+    pub fn moves_with_hit_levels(
+        &self,
+        character: Character,
+        hit_levels: &HitLevels,
+    ) -> Option<Vec<CharacterMove>> {
+        let moves = self.store.moves(character)?;
+
+        Some(
+            moves
+                .into_iter()
+                .filter(|m| m.hit_levels().and_then(Result::ok).as_ref() == Some(hit_levels))
+                .collect(),
+        )
+    }
+
+    /// Moves of `character` with a crush property of the given kind
+    pub fn moves_with_crush(
+        &self,
+        character: Character,
+        kind: CrushKind,
+    ) -> Option<Vec<CharacterMove>> {
+        let moves = self.store.moves(character)?;
+
+        Some(
+            moves
+                .into_iter()
+                .filter(|m| matches!(m.crushes(), Some(Ok(crushes)) if crushes.has(kind)))
+                .collect(),
+        )
+    }
+
+    /// Moves of `character` fast enough to punish a move that is `frames` on block,
+    /// most damaging first
+    pub fn punishers(&self, character: Character, frames: u32) -> Option<Vec<CharacterMove>> {
+        let moves = self.store.moves(character)?;
+
+        let mut punishers = moves
+            .into_iter()
+            .filter(|m| match m.parse_frames(FrameField::Startup) {
+                Some(Ok(startup)) => startup
+                    .first_frame()
+                    .is_some_and(|startup| startup > 0 && startup as u32 <= frames),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        sort_by_damage(&mut punishers);
+
+        Some(punishers)
+    }
+
+    /// Moves of `character` whose first hit is a low, most damaging first
+    pub fn lows_by_damage(&self, character: Character) -> Option<Vec<CharacterMove>> {
+        let moves = self.store.moves(character)?;
+
+        let mut lows = moves
+            .into_iter()
+            .filter(|m| match m.hit_levels() {
+                Some(Ok(hit_levels)) => hit_levels.0.first().is_some_and(HitLevel::is_low),
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        sort_by_damage(&mut lows);
+
+        Some(lows)
+    }
+
     pub fn query_move(&self, character: Character, query: &[String]) -> Option<CharacterMoveMatch> {
+        let moves = self.store.moves(character)?;
+        self.match_move(character, query, &moves)
+    }
+
+    /// Moves of `character` closest to what the user has typed so far, best first.
+    /// Without any input the movelist is suggested in order
+    pub fn suggest_moves(
+        &self,
+        character: Character,
+        partial: &str,
+        limit: usize,
+    ) -> Vec<MoveSuggestion> {
+        let index = self.store.move_index();
+        let moves = index.moves(character);
+        let partial = partial.trim_ascii();
+
+        if partial.is_empty() {
+            return moves
+                .iter()
+                .take(limit)
+                .map(|m| m.suggestion.clone())
+                .collect();
+        }
+
+        let query = MoveQuery::new(character, partial);
+        let mut scored = moves
+            .iter()
+            .map(|m| (self.matcher.score(&query, &m.keys), m))
+            .collect::<Vec<_>>();
+        // Stable, so equally close moves keep their movelist order
+        scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, m)| m.suggestion.clone())
+            .collect()
+    }
+
+    /// Past game versions that can be looked up, oldest first. Empty if the store doesn't
+    /// keep a history
+    pub async fn versions(&self) -> Vec<MoveVersion> {
+        match self.store.history() {
+            Some(history) => history.versions().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Like `query_move`, but in the data of a past game version. Returns `None` if
+    /// there's no such version
+    pub async fn query_move_at_version(
+        &self,
+        character: Character,
+        query: &[String],
+        version: &str,
+    ) -> Result<Option<(MoveVersion, Option<CharacterMoveMatch>)>> {
+        let Some(history) = self.store.history() else {
+            return Ok(None);
+        };
+        let Some(version) = history.find(version).await else {
+            return Ok(None);
+        };
+
+        let moves = history.moves(&version).await?;
+        let found = moves
+            .get(&character)
+            .and_then(|moves| self.match_move(character, query, moves));
+
+        Ok(Some((version, found)))
+    }
+
+    /// The move matching `query` in the current data together with how it changed over
+    /// the recorded versions
+    pub async fn move_revisions(
+        &self,
+        character: Character,
+        query: &[String],
+    ) -> Result<Option<(CharacterMoveMatch, Vec<(MoveVersion, MoveRevision)>)>> {
+        let Some(found) = self.query_move(character, query) else {
+            return Ok(None);
+        };
+        let revisions = match self.store.history() {
+            Some(history) => {
+                history
+                    .move_revisions(character, &found.character_move.id)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Some((found, revisions)))
+    }
+
+    fn match_move(
+        &self,
+        character: Character,
+        query: &[String],
+        moves: &[CharacterMove],
+    ) -> Option<CharacterMoveMatch> {
         let move_query = query
             .iter()
             .map(|q| q.trim_ascii())
             .collect::<Vec<_>>()
             .join(" ");
 
-        let moves = self.store.moves(character)?;
-
-        let id_match = self.matcher.match_by_id(character, &move_query, &moves);
+        let id_match = self.matcher.match_by_id(character, &move_query, moves);
         if let Some(ref m) = id_match
             && m.score >= 1f64
         {
             return id_match;
         }
 
-        let name_match = self.matcher.match_by_name(character, &move_query, &moves);
+        let name_match = self.matcher.match_by_name(character, &move_query, moves);
         if let Some(ref m) = name_match
             && m.score >= 1f64
         {
             return name_match;
         }
 
-        let alt_match = self.matcher.match_by_alt(character, &move_query, &moves);
+        let alt_match = self.matcher.match_by_alt(character, &move_query, moves);
         if let Some(ref m) = alt_match
             && m.score >= 1f64
         {
             return alt_match;
         }
 
-        let alias_match = self.matcher.match_by_alias(character, &move_query, &moves);
+        let alias_match = self.matcher.match_by_alias(character, &move_query, moves);
         if let Some(ref m) = alias_match
             && m.score >= 1f64
         {
@@ -67,13 +294,31 @@ impl<R: MoveRepository, M: MoveMatcher> FrameService<R, M> {
     }
 }
 
+/// Most damaging first, keeping the original order for moves that deal the same damage
+fn sort_by_damage(moves: &mut [CharacterMove]) {
+    moves.sort_by_key(|m| std::cmp::Reverse(m.total_damage()));
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
     use async_trait::async_trait;
     use rstest::*;
 
-    use crate::tekken::character_move::CharacterMove;
+    use wiremock::ResponseTemplate;
+
+    use crate::{
+        matchers::{MoveKeys, jaro_matcher::JaroMoveMatcher},
+        move_store::{
+            MoveMap,
+            history::{MoveHistory, MoveHistoryConfig},
+        },
+        repositories::{
+            wavu_client::WavuClientConfig, wavu_move_repository::WavuMoveRepository,
+            wavu_stand_in::WavuStandIn,
+        },
+        tekken::tracking::Tracking,
+    };
 
     use super::*;
 
@@ -95,9 +340,10 @@ mod tests {
             alt_score,
             alias_score,
         };
-        let service = FrameService::try_new(MockMoveRepository, mock_matcher)
-            .await
-            .unwrap();
+        let service =
+            FrameService::try_new(MockMoveRepository, mock_matcher, MoveStoreConfig::default())
+                .await
+                .unwrap();
         let query = vec!["bla".into()];
 
         let character_move = service.query_move(Character::Paul, &query).unwrap();
@@ -105,6 +351,428 @@ mod tests {
         assert_eq!(character_move.score, 1.0);
     }
 
+    fn unreadable_response() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw("{", "application/json")
+    }
+
+    async fn stand_in_frame_service(
+        wavu: &WavuStandIn,
+    ) -> Result<FrameService<WavuMoveRepository, JaroMoveMatcher>> {
+        let store_config = MoveStoreConfig {
+            fetch_attempts: 1,
+            ..Default::default()
+        };
+        FrameService::try_new(wavu.repository(), JaroMoveMatcher, store_config).await
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(Character::Kazuya, "flash punch combo", "Kazuya-1,1,2")]
+    #[case(Character::Kazuya, "ewgf", "Kazuya-f,n,d,df#2")]
+    #[case(Character::Kazuya, "df1", "Kazuya-df+1")]
+    #[case(Character::Paul, "qcf+2", "Paul-qcf+2")]
+    #[case(Character::Bryan, "uf4", "Bryan-uf+4")]
+    async fn test_query_move_from_stand_in(
+        #[case] character: Character,
+        #[case] query: &str,
+        #[case] expected_id: &str,
+    ) {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json", "bryan.json"])
+            .await;
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        let query = query.split(' ').map(String::from).collect::<Vec<_>>();
+        let found = service.query_move(character, &query).unwrap();
+
+        assert_eq!(found.character, character);
+        assert_eq!(found.character_move.id, expected_id);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(Character::Kazuya, "ewgf", "f,n,d,df#2")]
+    #[case(Character::Kazuya, "df1", "df+1")]
+    #[case(Character::Paul, "phoenix smasher", "qcf+2")]
+    async fn test_suggest_moves_from_stand_in(
+        #[case] character: Character,
+        #[case] partial: &str,
+        #[case] expected_value: &str,
+    ) {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json", "bryan.json"])
+            .await;
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        let suggestions = service.suggest_moves(character, partial, 5);
+
+        assert!(!suggestions.is_empty() && suggestions.len() <= 5);
+        assert_eq!(suggestions[0].value, expected_value);
+    }
+
+    #[tokio::test]
+    async fn test_suggests_movelist_without_input() {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let values = service
+            .suggest_moves(Character::Leroy, " ", 3)
+            .into_iter()
+            .map(|suggestion| suggestion.value)
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec!["1", "2", "3"]);
+        assert!(service.suggest_moves(Character::Leroy, "1", 0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_loads_characters_with_one_move_table_request() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves(0, &["kazuya.json", "paul.json", "bryan.json"])
+            .await;
+
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        // The move table, then the revisions of every movelist page
+        assert_eq!(wavu.request_count().await, 2);
+        assert!(
+            [Character::Kazuya, Character::Paul, Character::Bryan]
+                .into_iter()
+                .all(|c| service.is_character_available(c))
+        );
+        assert!(!service.is_character_available(Character::Leo));
+        assert!(
+            service
+                .query_move(Character::Paul, &["qcf+2".into()])
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_a_request_per_character() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves_response(0, unreadable_response())
+            .await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        // Every character's moves are followed by the revision of its movelist page
+        assert_eq!(
+            wavu.request_count().await,
+            1 + 2 * Character::iter().count()
+        );
+        assert!(
+            service
+                .query_move(Character::Kazuya, &["ewgf".into()])
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_request_per_character_when_wavu_is_down() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves_response(0, ResponseTemplate::new(503))
+            .await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+
+        let result = stand_in_frame_service(&wavu).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            wavu.request_count().await,
+            1 + WavuClientConfig::default().max_retries
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_move_from_stand_in_with_failing_characters() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves_response(0, unreadable_response())
+            .await;
+        wavu.serve_fixture(Character::Kazuya, "kazuya.json").await;
+        wavu.serve_response(Character::Bryan, ResponseTemplate::new(500))
+            .await;
+        wavu.serve_response(
+            Character::Paul,
+            ResponseTemplate::new(200).set_body_raw("{", "application/json"),
+        )
+        .await;
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        assert!(!service.is_character_available(Character::Bryan));
+        assert!(!service.is_character_available(Character::Paul));
+        assert!(service.is_character_available(Character::Kazuya));
+        assert!(service.is_character_available(Character::Leo));
+
+        let found = service.query_move(Character::Kazuya, &["1,1,2".into()]);
+        assert_eq!(found.unwrap().character_move.id, "Kazuya-1,1,2");
+    }
+
+    #[tokio::test]
+    async fn test_query_move_from_stand_in_with_empty_results() {
+        let wavu = WavuStandIn::start().await;
+        let service = stand_in_frame_service(&wavu).await.unwrap();
+
+        let found = service.query_move(Character::Kazuya, &["1,1,2".into()]);
+
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stand_in_unreachable_for_every_character() {
+        let wavu = WavuStandIn::start().await;
+        wavu.serve_all_moves_response(0, ResponseTemplate::new(503))
+            .await;
+        for character in Character::iter() {
+            wavu.serve_response(character, ResponseTemplate::new(503))
+                .await;
+        }
+
+        let result = stand_in_frame_service(&wavu).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_moves_weak_to_sidestep() {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let weak_left = service
+            .moves_weak_to_sidestep(Character::Leroy, SidestepDirection::Left)
+            .unwrap();
+        let weak_right = service
+            .moves_weak_to_sidestep(Character::Leroy, SidestepDirection::Right)
+            .unwrap();
+
+        let ids = |moves: Vec<CharacterMove>| moves.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids(weak_left), vec!["Leroy-1"]);
+        assert_eq!(ids(weak_right), vec!["Leroy-1", "Leroy-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_moves_with_hit_levels() {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let mid_lows = service
+            .moves_with_hit_levels(Character::Leroy, &HitLevels::parse("m,l").unwrap())
+            .unwrap();
+
+        let ids = mid_lows.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["Leroy-2"]);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(CrushKind::PowerCrush, vec!["Leroy-2", "Leroy-3"])]
+    #[case(CrushKind::JumpState, vec!["Leroy-3"])]
+    #[case(CrushKind::CrouchState, vec![])]
+    async fn test_moves_with_crush(#[case] kind: CrushKind, #[case] expected_ids: Vec<&str>) {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let moves = service.moves_with_crush(Character::Leroy, kind).unwrap();
+
+        let ids = moves.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[tokio::test]
+    #[rstest]
+    #[case(10, vec!["Leroy-2", "Leroy-1"])]
+    #[case(13, vec!["Leroy-3", "Leroy-2", "Leroy-1"])]
+    #[case(9, vec![])]
+    async fn test_punishers(#[case] frames: u32, #[case] expected_ids: Vec<&str>) {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let moves = service.punishers(Character::Leroy, frames).unwrap();
+
+        let ids = moves.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[tokio::test]
+    async fn test_lows_by_damage() {
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let moves = service.lows_by_damage(Character::Leroy).unwrap();
+
+        let ids = moves.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["Leroy-5", "Leroy-6"]);
+    }
+
+    #[tokio::test]
+    async fn test_query_move_at_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let history_config = |label: &str| MoveHistoryConfig {
+            label: Some(label.into()),
+            ..MoveHistoryConfig::new(dir.path())
+        };
+        let old_jab = CharacterMove {
+            id: "Leroy-1".into(),
+            startup_frames: Some("i12".into()),
+            ..Default::default()
+        };
+        MoveHistory::load(history_config("1.08"))
+            .await
+            .unwrap()
+            .record(
+                Utc::now(),
+                &MoveMap::from([(Character::Leroy, vec![old_jab])]),
+            )
+            .await
+            .unwrap();
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig {
+                history: Some(history_config("Season 2 / 2.00")),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let query = vec!["1".to_string()];
+
+        let (version, found) = service
+            .query_move_at_version(Character::Leroy, &query, "1.08")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(version.label, "1.08");
+        assert_eq!(
+            found.unwrap().character_move.startup_frames.as_deref(),
+            Some("i12")
+        );
+
+        let (version, found) = service
+            .query_move_at_version(Character::Leroy, &query, "2.00")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(version.label, "Season 2 / 2.00");
+        assert_eq!(
+            found.unwrap().character_move.startup_frames.as_deref(),
+            Some("i10")
+        );
+
+        assert!(
+            service
+                .query_move_at_version(Character::Leroy, &query, "3.00")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_revisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let history_config = |label: &str| MoveHistoryConfig {
+            label: Some(label.into()),
+            ..MoveHistoryConfig::new(dir.path())
+        };
+        MoveHistory::load(history_config("1.08"))
+            .await
+            .unwrap()
+            .record(Utc::now(), &MoveMap::from([(Character::Leroy, vec![])]))
+            .await
+            .unwrap();
+        let service = FrameService::try_new(
+            SampleMoveRepository,
+            JaroMoveMatcher,
+            MoveStoreConfig {
+                history: Some(history_config("2.00")),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let (found, revisions) = service
+            .move_revisions(Character::Leroy, &["1".into()])
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(found.character_move.id, "Leroy-1");
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].0.label, "2.00");
+        assert_eq!(revisions[0].1, MoveRevision::Added);
+        assert_eq!(service.versions().await.len(), 2);
+    }
+
+    struct SampleMoveRepository;
+
+    #[async_trait]
+    impl MoveRepository for SampleMoveRepository {
+        async fn character_moves(&self, character: Character) -> Result<Vec<CharacterMove>> {
+            let sample_move = |id: &str, target: &str, crush: &str, left, right| CharacterMove {
+                id: format!("{character}-{id}"),
+                target: Some(target.into()),
+                crush: Some(crush.into()),
+                tracking: Tracking::new(left, right),
+                ..Default::default()
+            };
+            let with_damage = |m: CharacterMove, startup: &str, damage: &str| CharacterMove {
+                startup_frames: Some(startup.into()),
+                damage: Some(damage.into()),
+                ..m
+            };
+
+            Ok(vec![
+                with_damage(sample_move("1", "h", "", Some("n"), Some("n")), "i10", "5"),
+                with_damage(
+                    sample_move("2", "m,l", "pc8~", Some("y"), Some("n")),
+                    "i10~11",
+                    "10,12",
+                ),
+                with_damage(
+                    sample_move("3", "m,l,m", "pc6~10, js12~", Some("y"), Some("y")),
+                    "i13",
+                    "10,12,20",
+                ),
+                sample_move("4", "x", "bogus", None, None),
+                with_damage(sample_move("5", "l", "", None, None), "i20", "18"),
+                with_damage(sample_move("6", "l", "", None, None), "i16", "bogus"),
+            ])
+        }
+    }
+
     struct MockMoveRepository;
 
     #[async_trait]
@@ -124,6 +792,10 @@ mod tests {
 
     #[allow(unused)]
     impl MoveMatcher for MockMoveMatcher {
+        fn score(&self, query: &MoveQuery, keys: &MoveKeys) -> f64 {
+            self.id_score
+        }
+
         fn match_by_id(
             &self,
             character: Character,
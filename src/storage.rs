@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Writes to a temporary file first and renames it over the old file, so a crash halfway
+/// through never leaves a truncated file behind. Creates missing parent directories
+pub async fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let tmp_path = path.with_extension("tmp");
+
+    tokio::fs::write(&tmp_path, bytes)
+        .await
+        .with_context(|| format!("failed to write {}", tmp_path.display()))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .with_context(|| format!("failed to move {} into place", path.display()))?;
+
+    Ok(())
+}
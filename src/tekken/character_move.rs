@@ -1,4 +1,24 @@
-#[derive(Debug, PartialEq, Eq, Hash, Default, Clone)]
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::tekken::{
+    character::Character,
+    crush::Crushes,
+    damage::Damage,
+    frames::{FrameField, FrameValue},
+    hit_level::HitLevels,
+    parse_error::ParseError,
+    provenance::Provenance,
+    tracking::{Tracking, TrackingSide},
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CharacterMove {
     pub id: String,
     pub name: Option<String>,
@@ -9,6 +29,7 @@ pub struct CharacterMove {
     pub target: Option<String>,
     pub damage: Option<String>,
     pub reach: Option<String>,
+    pub tracking: Tracking,
     pub startup_frames: Option<String>,
     pub recovery_frames: Option<String>,
     pub total_frames: Option<String>,
@@ -17,4 +38,266 @@ pub struct CharacterMove {
     pub on_hit: Option<String>,
     pub on_counter_hit: Option<String>,
     pub notes: Vec<String>,
+    /// Fields that were changed by a community override rather than coming from the
+    /// move's source
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub overridden: BTreeSet<MoveField>,
+    #[serde(skip_serializing_if = "Provenance::is_empty")]
+    pub provenance: Provenance,
+}
+
+/// The fields of a `CharacterMove` that are compared between refreshes. All but the
+/// parent and alternative inputs can be overridden
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum MoveField {
+    Name,
+    Input,
+    Alias,
+    Parent,
+    Alt,
+    HitLevel,
+    Damage,
+    Reach,
+    Tracking,
+    Crush,
+    Notes,
+    Frames(FrameField),
+}
+
+impl MoveField {
+    pub fn all() -> impl Iterator<Item = MoveField> {
+        [
+            MoveField::Name,
+            MoveField::Input,
+            MoveField::Alias,
+            MoveField::Parent,
+            MoveField::Alt,
+            MoveField::HitLevel,
+            MoveField::Damage,
+            MoveField::Reach,
+            MoveField::Tracking,
+            MoveField::Crush,
+            MoveField::Notes,
+        ]
+        .into_iter()
+        .chain(FrameField::iter().map(MoveField::Frames))
+    }
+}
+
+impl CharacterMove {
+    /// The field's value as text, empty if the move has none
+    pub fn field_value(&self, field: MoveField) -> String {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        match field {
+            MoveField::Name => optional(&self.name),
+            MoveField::Input => optional(&self.input),
+            MoveField::Alias => self.alias.join(", "),
+            MoveField::Parent => optional(&self.parent),
+            MoveField::Alt => self.alt.join(", "),
+            MoveField::HitLevel => optional(&self.target),
+            MoveField::Damage => optional(&self.damage),
+            MoveField::Reach => optional(&self.reach),
+            MoveField::Tracking => {
+                if !self.tracking.is_known() {
+                    return String::new();
+                }
+                let side = |side: &Option<TrackingSide>| {
+                    side.as_ref()
+                        .map(TrackingSide::to_string)
+                        .unwrap_or_else(|| "unknown".into())
+                };
+                format!(
+                    "left {}, right {}",
+                    side(&self.tracking.left),
+                    side(&self.tracking.right)
+                )
+            }
+            MoveField::Crush => optional(&self.crush),
+            MoveField::Notes => self.notes.join("; "),
+            MoveField::Frames(field) => self.frames(field).unwrap_or_default().into(),
+        }
+    }
+
+    pub fn is_overridden(&self, field: MoveField) -> bool {
+        self.overridden.contains(&field)
+    }
+
+    /// The raw value of a frame data field
+    pub fn frames(&self, field: FrameField) -> Option<&str> {
+        let value = match field {
+            FrameField::Startup => &self.startup_frames,
+            FrameField::OnBlock => &self.on_block,
+            FrameField::OnHit => &self.on_hit,
+            FrameField::OnCounterHit => &self.on_counter_hit,
+            FrameField::Recovery => &self.recovery_frames,
+            FrameField::Total => &self.total_frames,
+        };
+
+        value.as_deref().filter(|s| !s.trim().is_empty())
+    }
+
+    /// Returns `None` if the move has no value for the field
+    pub fn parse_frames(&self, field: FrameField) -> Option<Result<FrameValue, ParseError>> {
+        self.frames(field).map(FrameValue::parse)
+    }
+
+    /// Returns `None` if the move has no hit level
+    pub fn hit_levels(&self) -> Option<Result<HitLevels, ParseError>> {
+        self.target
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(HitLevels::parse)
+    }
+
+    /// Returns `None` if the move has no damage
+    pub fn parse_damage(&self) -> Option<Result<Damage, ParseError>> {
+        self.damage
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(Damage::parse)
+    }
+
+    /// Total damage of the move, 0 if it's unknown
+    pub fn total_damage(&self) -> u32 {
+        match self.parse_damage() {
+            Some(Ok(damage)) => damage.total(),
+            _ => 0,
+        }
+    }
+
+    /// Returns `None` if the move has no crush properties
+    pub fn crushes(&self) -> Option<Result<Crushes, ParseError>> {
+        self.crush
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(Crushes::parse)
+    }
+
+    /// Values of the move that are there but can't be parsed
+    pub fn value_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for field in FrameField::iter() {
+            if let Some(Err(e)) = self.parse_frames(field) {
+                problems.push(format!("{field}: {e}"));
+            }
+        }
+        if let Some(Err(e)) = self.hit_levels() {
+            problems.push(format!("{}: {e}", MoveField::HitLevel));
+        }
+        if let Some(Err(e)) = self.parse_damage() {
+            problems.push(format!("{}: {e}", MoveField::Damage));
+        }
+        if let Some(Err(e)) = self.crushes() {
+            problems.push(format!("{}: {e}", MoveField::Crush));
+        }
+
+        problems
+    }
+}
+
+/// Moves of a character's movelist that are unusable or have values that can't be
+/// parsed, as the index of the move and what's wrong with it. Moves without an id
+/// aren't checked any further
+pub fn movelist_problems(character: Character, moves: &[CharacterMove]) -> Vec<(usize, String)> {
+    let prefix = character.move_id_prefix();
+    let mut seen_ids = HashSet::new();
+    let mut problems = Vec::new();
+
+    for (i, m) in moves.iter().enumerate() {
+        if m.id.trim().is_empty() {
+            problems.push((i, "id is empty".into()));
+            continue;
+        }
+        if !m.id.starts_with(&prefix) {
+            problems.push((i, format!("id doesn't start with \"{prefix}\"")));
+        }
+        if !seen_ids.insert(&m.id) {
+            problems.push((i, "id is used more than once".into()));
+        }
+
+        problems.extend(m.value_problems().into_iter().map(|message| (i, message)));
+    }
+
+    problems
+}
+
+impl Display for MoveField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            MoveField::Name => "Name",
+            MoveField::Input => "Input",
+            MoveField::Alias => "Alias",
+            MoveField::Parent => "Parent",
+            MoveField::Alt => "Alternative Inputs",
+            MoveField::HitLevel => "Hit Level",
+            MoveField::Damage => "Damage",
+            MoveField::Reach => "Reach",
+            MoveField::Tracking => "Tracking",
+            MoveField::Crush => "Crush",
+            MoveField::Notes => "Notes",
+            MoveField::Frames(field) => return write!(f, "{field}"),
+        };
+
+        write!(f, "{str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frames() {
+        let character_move = CharacterMove {
+            startup_frames: Some("i15~16".into()),
+            on_block: Some("-13".into()),
+            on_hit: Some("".into()),
+            on_counter_hit: Some("+5x".into()),
+            ..Default::default()
+        };
+
+        let startup = character_move.parse_frames(FrameField::Startup);
+        let on_block = character_move.parse_frames(FrameField::OnBlock);
+
+        assert_eq!(startup.unwrap().unwrap().first_frame(), Some(15));
+        assert_eq!(on_block.unwrap().unwrap().first_frame(), Some(-13));
+        assert!(character_move.parse_frames(FrameField::OnHit).is_none());
+        assert!(character_move.parse_frames(FrameField::Recovery).is_none());
+        assert!(
+            character_move
+                .parse_frames(FrameField::OnCounterHit)
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_movelist_problems() {
+        let moves = [
+            ("Paul-1", "+5"),
+            ("", "+5"),
+            ("Kazuya-1", "+5"),
+            ("Paul-1", "+5x"),
+        ]
+        .map(|(id, on_counter_hit)| CharacterMove {
+            id: id.into(),
+            on_counter_hit: Some(on_counter_hit.into()),
+            ..Default::default()
+        });
+
+        let problems = movelist_problems(Character::Paul, &moves);
+
+        let entries = problems.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        assert_eq!(entries, vec![1, 2, 3, 3]);
+        assert_eq!(problems[0].1, "id is empty");
+        assert_eq!(problems[1].1, "id doesn't start with \"Paul-\"");
+        assert_eq!(problems[2].1, "id is used more than once");
+        assert!(
+            problems[3]
+                .1
+                .starts_with(&FrameField::OnCounterHit.to_string())
+        );
+    }
 }
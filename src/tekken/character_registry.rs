@@ -0,0 +1,314 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::OnceLock,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use strsim::jaro_winkler;
+
+use crate::tekken::{TEKKEN_RED, character::normalize_name};
+
+/// Roster the bot ships with, used unless another one is installed at startup
+const BUILTIN_CHARACTERS: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/characters.toml"));
+
+static REGISTRY: OnceLock<CharacterRegistry> = OnceLock::new();
+
+/// Everything the bot knows about a character, from the character registry file
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct CharacterInfo {
+    /// Name used in saved data, e.g. "ArmorKing"
+    pub key: String,
+    /// Name shown to users, e.g. "Armor King"
+    pub name: String,
+    /// Name Wavu uses in move ids and page names, if different from `name`
+    pub wavu_name: Option<String>,
+    /// Name in okizeme.gg links, if different from the lowercase `name`
+    pub okizeme_slug: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub portrait: Option<String>,
+    pub colour: Option<u32>,
+}
+
+/// The playable characters, loaded from a data file so new characters can be added
+/// without a code change
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CharacterRegistry {
+    characters: Vec<CharacterInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CharacterRegistryFile {
+    characters: Vec<CharacterInfo>,
+}
+
+impl CharacterInfo {
+    pub fn wavu_name(&self) -> &str {
+        self.wavu_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn okizeme_slug(&self) -> String {
+        self.okizeme_slug
+            .clone()
+            .unwrap_or_else(|| self.name.to_lowercase())
+    }
+
+    pub fn colour(&self) -> u32 {
+        self.colour.unwrap_or(TEKKEN_RED)
+    }
+
+    /// Every name the character can be looked up by
+    fn names(&self) -> impl Iterator<Item = &str> {
+        [self.key.as_str(), &self.name, self.wavu_name()]
+            .into_iter()
+            .chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+impl CharacterRegistry {
+    /// Checks that every character can be told apart by their key, names and aliases
+    pub fn new(characters: Vec<CharacterInfo>) -> Result<Self> {
+        if characters.is_empty() {
+            bail!("there are no characters");
+        }
+
+        let mut keys = HashSet::new();
+        let mut owners = HashMap::new();
+        for character in &characters {
+            if character.key.is_empty() || normalize_name(&character.name).is_empty() {
+                bail!("every character needs a key and a name");
+            }
+            if !keys.insert(&character.key) {
+                bail!("{} is in the roster more than once", character.key);
+            }
+            for name in character.names() {
+                let normalized = normalize_name(name);
+                if normalized.is_empty() {
+                    bail!("{} has an empty alias", character.key);
+                }
+                match owners.insert(normalized, &character.key) {
+                    Some(owner) if owner != &character.key => {
+                        bail!("\"{name}\" is used by both {owner} and {}", character.key)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self { characters })
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        let file: CharacterRegistryFile = toml::from_str(toml)?;
+        Self::new(file.characters)
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let toml = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        Self::from_toml(&toml).with_context(|| format!("{} is invalid", path.display()))
+    }
+
+    pub fn builtin() -> Self {
+        Self::from_toml(BUILTIN_CHARACTERS).expect("the built-in character registry is invalid")
+    }
+
+    pub fn characters(&self) -> &[CharacterInfo] {
+        &self.characters
+    }
+
+    pub fn get(&self, key: &str) -> Option<&CharacterInfo> {
+        self.characters
+            .iter()
+            .find(|character| character.key == key)
+    }
+
+    /// Looks a character up by their key, name or one of their aliases, ignoring case,
+    /// spaces and dashes
+    pub fn find(&self, name: &str) -> Option<&CharacterInfo> {
+        let name = normalize_name(name);
+        if name.is_empty() {
+            return None;
+        }
+
+        self.characters.iter().find(|character| {
+            character
+                .names()
+                .any(|candidate| normalize_name(candidate) == name)
+        })
+    }
+
+    /// Every character with how close their name or closest alias is to `name`, best
+    /// first. Names starting with `name` count as a perfect match, so partial input
+    /// ranks as expected. Characters that are equally close keep the registry's order
+    pub fn closest(&self, name: &str) -> Vec<(&CharacterInfo, f64)> {
+        let name = normalize_name(name);
+
+        let mut scored = self
+            .characters
+            .iter()
+            .map(|character| {
+                let score = character
+                    .names()
+                    .map(|candidate| {
+                        let candidate = normalize_name(candidate);
+                        if candidate.starts_with(&name) {
+                            1.0
+                        } else {
+                            jaro_winkler(&candidate, &name)
+                        }
+                    })
+                    .fold(0.0, f64::max);
+                (character, score)
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        scored
+    }
+
+    /// Names from another source, such as Wavu, that don't match any character
+    pub fn unknown_names<'a>(&self, names: &'a [String]) -> Vec<&'a str> {
+        names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| self.find(name).is_none())
+            .collect()
+    }
+}
+
+/// Makes `registry` the roster for the rest of the program. Has to happen before
+/// anything looks at a character
+pub fn install(registry: CharacterRegistry) -> Result<()> {
+    if REGISTRY.set(registry).is_err() {
+        bail!("the character registry was already in use before it was installed");
+    }
+
+    Ok(())
+}
+
+/// The installed roster, the built-in one if none was installed
+pub fn registry() -> &'static CharacterRegistry {
+    REGISTRY.get_or_init(CharacterRegistry::builtin)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn info(key: &str, name: &str, aliases: &[&str]) -> CharacterInfo {
+        CharacterInfo {
+            key: key.into(),
+            name: name.into(),
+            wavu_name: None,
+            okizeme_slug: None,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            portrait: None,
+            colour: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_registry_is_valid() {
+        let registry = CharacterRegistry::builtin();
+
+        assert_eq!(registry.characters().len(), 40);
+        assert!(
+            registry
+                .characters()
+                .iter()
+                .all(|character| character.portrait.is_some())
+        );
+    }
+
+    #[rstest]
+    #[case("Armor King", Some("ArmorKing"))]
+    #[case("armorking", Some("ArmorKing"))]
+    #[case("ak", Some("ArmorKing"))]
+    #[case("jack8", Some("Jack8"))]
+    #[case("Jack-8", Some("Jack8"))]
+    #[case("mz", Some("MiaryZo"))]
+    #[case("Armor", None)]
+    #[case("", None)]
+    fn test_find(#[case] name: &str, #[case] expected: Option<&str>) {
+        let registry = CharacterRegistry::builtin();
+
+        assert_eq!(
+            registry.find(name).map(|character| character.key.as_str()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_reads_optional_fields() {
+        let registry = CharacterRegistry::from_toml(
+            r#"
+            [[characters]]
+            key = "Anna"
+            name = "Anna"
+
+            [[characters]]
+            key = "NewChallenger"
+            name = "New Challenger"
+            wavu_name = "Challenger"
+            okizeme_slug = "new-challenger"
+            aliases = ["nc"]
+            colour = 0x123456
+            "#,
+        )
+        .unwrap();
+
+        let anna = registry.get("Anna").unwrap();
+        assert_eq!(anna.wavu_name(), "Anna");
+        assert_eq!(anna.okizeme_slug(), "anna");
+        assert_eq!(anna.colour(), TEKKEN_RED);
+        let challenger = registry.find("challenger").unwrap();
+        assert_eq!(challenger.key, "NewChallenger");
+        assert_eq!(challenger.okizeme_slug(), "new-challenger");
+        assert_eq!(challenger.colour(), 0x123456);
+    }
+
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![info("Jin", "Jin", &[]), info("Jin2", "JIN", &[])])]
+    #[case(vec![info("Jin", "Jin", &[]), info("Jin", "Jin Kazama", &[])])]
+    #[case(vec![info("Kazuya", "Kazuya", &["kaz"]), info("Kazumi", "Kazumi", &["Kaz"])])]
+    #[case(vec![info("Lee", "Lee", &[" - "])])]
+    #[case(vec![info("", "Nobody", &[])])]
+    fn test_rejects_ambiguous_rosters(#[case] characters: Vec<CharacterInfo>) {
+        assert!(CharacterRegistry::new(characters).is_err());
+    }
+
+    #[rstest]
+    #[case("", "Alisa")]
+    #[case("arm", "ArmorKing")]
+    #[case("armr king", "ArmorKing")]
+    #[case("dj", "DevilJin")]
+    #[case("yoshi", "Yoshimitsu")]
+    #[case("Kazyua", "Kazuya")]
+    #[case("drag", "Dragunov")]
+    fn test_closest(#[case] name: &str, #[case] expected: &str) {
+        let registry = CharacterRegistry::builtin();
+
+        let closest = registry.closest(name);
+
+        assert_eq!(closest.len(), registry.characters().len());
+        assert_eq!(closest[0].0.key, expected);
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        let registry = CharacterRegistry::builtin();
+        let names = vec!["Paul".to_string(), "Jack-8".into(), "Anakin".into()];
+
+        assert_eq!(registry.unknown_names(&names), vec!["Anakin"]);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Display;
+
+use crate::tekken::parse_error::ParseError;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, poise::ChoiceParameter)]
+pub enum CrushKind {
+    /// "js", airborne, crushes lows
+    #[name = "low"]
+    JumpState,
+    /// "cs", crouching, crushes highs
+    #[name = "high"]
+    CrouchState,
+    /// "pc", absorbs highs and mids
+    #[name = "power"]
+    PowerCrush,
+}
+
+/// Frames a crush property is active on. An open end means it lasts until the move
+/// recovers, e.g. "js8~"
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct CrushWindow {
+    pub start: Option<u32>,
+    pub end: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Crush {
+    pub kind: CrushKind,
+    pub window: CrushWindow,
+}
+
+/// Every crush property of a move, parsed from values such as "js8~25" or "cs6~, pc8~"
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Crushes(pub Vec<Crush>);
+
+impl Crushes {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        if s.trim().is_empty() {
+            return Err(ParseError::new(s, "value is empty"));
+        }
+
+        s.split([',', ' '])
+            .filter(|crush| !crush.is_empty())
+            .map(|crush| Crush::parse(crush).map_err(|reason| ParseError::new(s, reason)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Crushes)
+    }
+
+    pub fn has(&self, kind: CrushKind) -> bool {
+        self.0.iter().any(|crush| crush.kind == kind)
+    }
+}
+
+impl Crush {
+    fn parse(s: &str) -> Result<Self, String> {
+        let kind_len = s
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(s.len());
+
+        let kind = match s[..kind_len].to_ascii_lowercase().as_str() {
+            "js" => CrushKind::JumpState,
+            "cs" => CrushKind::CrouchState,
+            "pc" => CrushKind::PowerCrush,
+            other => return Err(format!("unknown crush \"{other}\"")),
+        };
+
+        let window = CrushWindow::parse(&s[kind_len..])
+            .ok_or_else(|| format!("invalid frames in \"{s}\""))?;
+
+        Ok(Self { kind, window })
+    }
+}
+
+impl CrushWindow {
+    /// Parses "8~25", "8~", "~25", "8" or nothing
+    fn parse(s: &str) -> Option<Self> {
+        let parse_frame = |frame: &str| match frame {
+            "" => Some(None),
+            _ => frame.parse().ok().map(Some),
+        };
+
+        match s.split_once('~') {
+            Some((start, end)) => Some(Self {
+                start: parse_frame(start)?,
+                end: parse_frame(end)?,
+            }),
+            None => {
+                let frame = parse_frame(s)?;
+                Some(Self {
+                    start: frame,
+                    end: frame,
+                })
+            }
+        }
+    }
+}
+
+impl Display for CrushKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            CrushKind::JumpState => "Low crush",
+            CrushKind::CrouchState => "High crush",
+            CrushKind::PowerCrush => "Power crush",
+        };
+
+        write!(f, "{str}")
+    }
+}
+
+impl Display for CrushWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) if start == end => write!(f, "on frame {start}"),
+            (Some(start), Some(end)) => write!(f, "frames {start}~{end}"),
+            (Some(start), None) => write!(f, "from frame {start}"),
+            (None, Some(end)) => write!(f, "until frame {end}"),
+            (None, None) => write!(f, "whole move"),
+        }
+    }
+}
+
+impl Display for Crush {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind, self.window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn crush(kind: CrushKind, start: Option<u32>, end: Option<u32>) -> Crush {
+        Crush {
+            kind,
+            window: CrushWindow { start, end },
+        }
+    }
+
+    #[rstest]
+    #[case("js8~25", vec![crush(CrushKind::JumpState, Some(8), Some(25))])]
+    #[case("cs6~", vec![crush(CrushKind::CrouchState, Some(6), None)])]
+    #[case("pc8~", vec![crush(CrushKind::PowerCrush, Some(8), None)])]
+    #[case("pc", vec![crush(CrushKind::PowerCrush, None, None)])]
+    #[case("JS10", vec![crush(CrushKind::JumpState, Some(10), Some(10))])]
+    #[case("cs~12", vec![crush(CrushKind::CrouchState, None, Some(12))])]
+    #[case(
+        "cs6~17, js20~",
+        vec![
+            crush(CrushKind::CrouchState, Some(6), Some(17)),
+            crush(CrushKind::JumpState, Some(20), None),
+        ]
+    )]
+    #[case(
+        "pc8~ js9~",
+        vec![
+            crush(CrushKind::PowerCrush, Some(8), None),
+            crush(CrushKind::JumpState, Some(9), None),
+        ]
+    )]
+    fn test_parse(#[case] s: &str, #[case] expected: Vec<Crush>) {
+        assert_eq!(Crushes::parse(s).unwrap(), Crushes(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("xs8~")]
+    #[case("js8~a")]
+    #[case("js-8")]
+    #[case("8~25")]
+    fn test_parse_errors(#[case] s: &str) {
+        assert_eq!(Crushes::parse(s).unwrap_err().value, s);
+    }
+
+    #[rstest]
+    #[case("js8~25", "Low crush frames 8~25")]
+    #[case("cs6~", "High crush from frame 6")]
+    #[case("pc", "Power crush whole move")]
+    #[case("js10", "Low crush on frame 10")]
+    fn test_display(#[case] s: &str, #[case] expected: &str) {
+        let crushes = Crushes::parse(s).unwrap();
+
+        assert_eq!(crushes.0[0].to_string(), expected);
+    }
+}
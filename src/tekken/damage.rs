@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::tekken::parse_error::ParseError;
+
+/// Damage of every hit of a move or string, parsed from values such as "10,12,20"
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Damage(pub Vec<u32>);
+
+impl Damage {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        if s.trim().is_empty() {
+            return Err(ParseError::new(s, "value is empty"));
+        }
+
+        s.split(',')
+            .map(|hit| {
+                hit.trim()
+                    .parse()
+                    .map_err(|_| ParseError::new(s, format!("invalid damage \"{hit}\"")))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Damage)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.0.iter().sum()
+    }
+}
+
+/// Shows the breakdown and the total for strings, e.g. "10 + 12 + 20 = 42"
+impl Display for Damage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.len() < 2 {
+            return write!(f, "{}", self.total());
+        }
+
+        let hits = self
+            .0
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        write!(f, "{hits} = {}", self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("10", vec![10], 10)]
+    #[case("10,12,20", vec![10, 12, 20], 42)]
+    #[case("5, 8, 18", vec![5, 8, 18], 31)]
+    #[case("0", vec![0], 0)]
+    fn test_parse(#[case] s: &str, #[case] hits: Vec<u32>, #[case] total: u32) {
+        let damage = Damage::parse(s).unwrap();
+
+        assert_eq!(damage.0, hits);
+        assert_eq!(damage.total(), total);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("10,,12")]
+    #[case("-5")]
+    #[case("ten")]
+    fn test_parse_errors(#[case] s: &str) {
+        assert_eq!(Damage::parse(s).unwrap_err().value, s);
+    }
+
+    #[rstest]
+    #[case("25", "25")]
+    #[case("10,12,20", "10 + 12 + 20 = 42")]
+    fn test_display(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(Damage::parse(s).unwrap().to_string(), expected);
+    }
+}
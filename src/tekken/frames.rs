@@ -0,0 +1,378 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::tekken::parse_error::ParseError;
+
+/// A parsed frame data value such as "i15~16", "-12", "+31a(+24)" or "r30 FC". Strings
+/// can have a value per hit separated by commas, e.g. "i10,i12"
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FrameValue {
+    /// The value as it was written, for display
+    pub raw: String,
+    pub hits: Vec<FrameHit>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct FrameHit {
+    /// `None` when the value only consists of states, e.g. "KND"
+    pub frames: Option<FrameRange>,
+    pub markers: Vec<FrameMarker>,
+    /// The value in parentheses, e.g. the +24 in "+31a(+24)"
+    pub extra: Option<FrameRange>,
+    /// States the value ends in, e.g. the FC in "r30 FC"
+    pub states: Vec<String>,
+}
+
+/// Inclusive range of frames, a single frame has `min == max`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FrameRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum FrameMarker {
+    /// "a", the opponent is launched
+    Launch,
+    /// "d", the opponent is knocked down
+    Knockdown,
+    /// "c", the opponent is forced into crouch
+    ForcedCrouch,
+    /// "s", the opponent is stunned
+    Stun,
+    /// "cs", the opponent crumples
+    Crumple,
+}
+
+/// The frame data fields of a `CharacterMove`
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, EnumIter, Serialize, Deserialize,
+)]
+pub enum FrameField {
+    Startup,
+    OnBlock,
+    OnHit,
+    OnCounterHit,
+    Recovery,
+    Total,
+}
+
+impl FrameValue {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let error = |reason: String| ParseError::new(s, reason);
+
+        if s.trim().is_empty() {
+            return Err(error("value is empty".into()));
+        }
+
+        let hits = split_hits(s)
+            .into_iter()
+            .map(|hit| FrameHit::parse(hit).map_err(&error))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            raw: s.into(),
+            hits,
+        })
+    }
+
+    /// Frames of the first hit, e.g. 15 for "i15~16". This is the value people mean
+    /// when comparing startup or frame advantage
+    pub fn first_frame(&self) -> Option<i32> {
+        self.hits.first()?.frames.map(|f| f.min)
+    }
+
+    /// Frames of the last hit, e.g. what a whole string is on block
+    pub fn last_frame(&self) -> Option<i32> {
+        self.hits.last()?.frames.map(|f| f.min)
+    }
+
+    pub fn has_marker(&self, marker: FrameMarker) -> bool {
+        self.hits.iter().any(|h| h.markers.contains(&marker))
+    }
+}
+
+impl FrameHit {
+    fn parse(s: &str) -> Result<Self, String> {
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err("empty hit between commas".into());
+        }
+
+        // Startup and recovery are prefixed with "i" and "r"
+        if let Some(stripped) = rest.strip_prefix(['i', 'r'])
+            && stripped.starts_with(|c: char| c.is_ascii_digit() || is_sign(c))
+        {
+            rest = stripped;
+        }
+
+        let frames = parse_range(&mut rest)?;
+
+        let mut markers = Vec::new();
+        if frames.is_some() {
+            let marker_len = rest
+                .find(|c: char| !c.is_ascii_lowercase())
+                .unwrap_or(rest.len());
+            markers = parse_markers(&rest[..marker_len])?;
+            rest = &rest[marker_len..];
+        }
+
+        let mut extra = None;
+        rest = rest.trim_start();
+        if let Some(inner) = rest.strip_prefix('(') {
+            let Some(end) = inner.find(')') else {
+                return Err("unclosed parenthesis".into());
+            };
+            let mut extra_str = inner[..end].trim();
+            extra = parse_range(&mut extra_str)?;
+            if extra.is_none() || !extra_str.trim().is_empty() {
+                return Err(format!(
+                    "unexpected value in parentheses \"{}\"",
+                    &inner[..end]
+                ));
+            }
+            rest = &inner[end + 1..];
+        }
+
+        let mut states = Vec::new();
+        for state in rest.split_whitespace() {
+            if !state
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '!')
+                || !state.starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                return Err(format!("unexpected \"{state}\""));
+            }
+            states.push(state.to_string());
+        }
+
+        if frames.is_none() && states.is_empty() {
+            return Err(format!("no frames in \"{}\"", s.trim()));
+        }
+
+        Ok(Self {
+            frames,
+            markers,
+            extra,
+            states,
+        })
+    }
+}
+
+impl FrameRange {
+    pub fn single(frame: i32) -> Self {
+        Self {
+            min: frame,
+            max: frame,
+        }
+    }
+}
+
+/// Splits on commas outside of parentheses
+fn split_hits(s: &str) -> Vec<&str> {
+    let mut hits = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                hits.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    hits.push(&s[start..]);
+
+    hits
+}
+
+fn is_sign(c: char) -> bool {
+    matches!(c, '+' | '-' | '−' | '±')
+}
+
+/// Parses "15", "+5", "-9~-8" or "15~16" from the start of `s` and advances it past
+/// the range. Returns `None` without advancing if `s` doesn't start with a number
+fn parse_range(s: &mut &str) -> Result<Option<FrameRange>, String> {
+    let Some(min) = parse_number(s) else {
+        return Ok(None);
+    };
+
+    let mut max = min;
+    let after_min = s.trim_start();
+    if let Some(after_tilde) = after_min.strip_prefix('~') {
+        let mut after_tilde = after_tilde.trim_start();
+        max =
+            parse_number(&mut after_tilde).ok_or_else(|| format!("range \"{min}~\" has no end"))?;
+        *s = after_tilde;
+    }
+
+    Ok(Some(FrameRange {
+        min: min.min(max),
+        max: min.max(max),
+    }))
+}
+
+fn parse_number(s: &mut &str) -> Option<i32> {
+    let mut chars = s.chars();
+    let (negative, unsigned) = match chars.next()? {
+        '-' | '−' => (true, chars.as_str()),
+        '+' | '±' => (false, chars.as_str()),
+        _ => (false, *s),
+    };
+
+    let digits_len = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    if digits_len == 0 {
+        return None;
+    }
+
+    let value: i32 = unsigned[..digits_len].parse().ok()?;
+    *s = &unsigned[digits_len..];
+
+    Some(if negative { -value } else { value })
+}
+
+fn parse_markers(s: &str) -> Result<Vec<FrameMarker>, String> {
+    match s {
+        "" => Ok(Vec::new()),
+        "cs" => Ok(vec![FrameMarker::Crumple]),
+        _ => s
+            .chars()
+            .map(|c| match c {
+                'a' => Ok(FrameMarker::Launch),
+                'd' => Ok(FrameMarker::Knockdown),
+                'c' => Ok(FrameMarker::ForcedCrouch),
+                's' => Ok(FrameMarker::Stun),
+                _ => Err(format!("unknown marker \"{c}\"")),
+            })
+            .collect(),
+    }
+}
+
+impl Display for FrameField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            FrameField::Startup => "Startup",
+            FrameField::OnBlock => "On Block",
+            FrameField::OnHit => "On Hit",
+            FrameField::OnCounterHit => "On Counter Hit",
+            FrameField::Recovery => "Recovery",
+            FrameField::Total => "Total",
+        };
+
+        write!(f, "{str}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn hit(frames: (i32, i32)) -> FrameHit {
+        FrameHit {
+            frames: Some(FrameRange {
+                min: frames.0,
+                max: frames.1,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case("i15", vec![hit((15, 15))])]
+    #[case("i15~16", vec![hit((15, 16))])]
+    #[case("-12", vec![hit((-12, -12))])]
+    #[case("+5", vec![hit((5, 5))])]
+    #[case("0", vec![hit((0, 0))])]
+    #[case("-9~-8", vec![hit((-9, -8))])]
+    #[case("+4~+6", vec![hit((4, 6))])]
+    #[case("-9 ~ -8", vec![hit((-9, -8))])]
+    #[case("i10,i12", vec![hit((10, 10)), hit((12, 12))])]
+    #[case("i10, i15~16", vec![hit((10, 10)), hit((15, 16))])]
+    fn test_parse_frames(#[case] s: &str, #[case] expected: Vec<FrameHit>) {
+        let value = FrameValue::parse(s).unwrap();
+
+        assert_eq!(value.raw, s);
+        assert_eq!(value.hits, expected);
+    }
+
+    #[rstest]
+    #[case("+5c", vec![FrameMarker::ForcedCrouch])]
+    #[case("+27d", vec![FrameMarker::Knockdown])]
+    #[case("+31a", vec![FrameMarker::Launch])]
+    #[case("+14s", vec![FrameMarker::Stun])]
+    #[case("+15cs", vec![FrameMarker::Crumple])]
+    #[case("+8", vec![])]
+    fn test_parse_markers(#[case] s: &str, #[case] expected: Vec<FrameMarker>) {
+        let value = FrameValue::parse(s).unwrap();
+
+        assert_eq!(value.hits[0].markers, expected);
+    }
+
+    #[test]
+    fn test_parse_extra_value() {
+        let value = FrameValue::parse("+31a(+24)").unwrap();
+
+        assert_eq!(
+            value.hits,
+            vec![FrameHit {
+                frames: Some(FrameRange::single(31)),
+                markers: vec![FrameMarker::Launch],
+                extra: Some(FrameRange::single(24)),
+                states: vec![],
+            }]
+        );
+        assert!(value.has_marker(FrameMarker::Launch));
+    }
+
+    #[test]
+    fn test_parse_extra_value_with_space() {
+        let value = FrameValue::parse("+22a (+12)").unwrap();
+
+        assert_eq!(value.hits[0].extra, Some(FrameRange::single(12)));
+    }
+
+    #[rstest]
+    #[case("r30 FC", Some(30), vec!["FC"])]
+    #[case("+4 BT", Some(4), vec!["BT"])]
+    #[case("-12 SEN", Some(-12), vec!["SEN"])]
+    #[case("KND", None, vec!["KND"])]
+    fn test_parse_states(#[case] s: &str, #[case] frames: Option<i32>, #[case] states: Vec<&str>) {
+        let value = FrameValue::parse(s).unwrap();
+
+        assert_eq!(value.first_frame(), frames);
+        assert_eq!(value.hits[0].states, states);
+    }
+
+    #[test]
+    fn test_first_and_last_frame() {
+        let value = FrameValue::parse("i10,i12~13,i20").unwrap();
+
+        assert_eq!(value.first_frame(), Some(10));
+        assert_eq!(value.last_frame(), Some(20));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("   ")]
+    #[case("i10,,i12")]
+    #[case("+5x")]
+    #[case("+31a(+24")]
+    #[case("+31a(oops)")]
+    #[case("i15~")]
+    #[case("?")]
+    #[case("+5 (see notes)")]
+    fn test_parse_errors(#[case] s: &str) {
+        let error = FrameValue::parse(s).unwrap_err();
+
+        assert_eq!(error.value, s);
+    }
+}
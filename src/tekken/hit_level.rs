@@ -0,0 +1,185 @@
+use std::fmt::Display;
+
+use crate::tekken::parse_error::ParseError;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum HitLevel {
+    High,
+    Mid,
+    Low,
+    SpecialMid,
+    SpecialLow,
+    HighThrow,
+    MidThrow,
+    LowThrow,
+    Unblockable,
+}
+
+/// The hit level of every hit of a move or string, parsed from targets such as
+/// "h,m,l" or "m,th"
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct HitLevels(pub Vec<HitLevel>);
+
+impl HitLevel {
+    fn parse(s: &str) -> Option<Self> {
+        let level = match s.trim().to_ascii_lowercase().as_str() {
+            "h" => HitLevel::High,
+            "m" => HitLevel::Mid,
+            "l" => HitLevel::Low,
+            "sm" => HitLevel::SpecialMid,
+            "sl" => HitLevel::SpecialLow,
+            "t" | "th" => HitLevel::HighThrow,
+            "tm" => HitLevel::MidThrow,
+            "tl" => HitLevel::LowThrow,
+            "ub" | "!" => HitLevel::Unblockable,
+            s if s.ends_with('!') => HitLevel::Unblockable,
+            _ => return None,
+        };
+
+        Some(level)
+    }
+
+    /// Whether the hit whiffs on a crouching opponent
+    pub fn is_duckable(&self) -> bool {
+        matches!(self, HitLevel::High | HitLevel::HighThrow)
+    }
+
+    /// Whether the hit has to be blocked crouching
+    pub fn is_low(&self) -> bool {
+        matches!(
+            self,
+            HitLevel::Low | HitLevel::SpecialLow | HitLevel::LowThrow
+        )
+    }
+
+    fn notation(&self) -> &'static str {
+        match self {
+            HitLevel::High => "h",
+            HitLevel::Mid => "m",
+            HitLevel::Low => "l",
+            HitLevel::SpecialMid => "sm",
+            HitLevel::SpecialLow => "sl",
+            HitLevel::HighThrow => "th",
+            HitLevel::MidThrow => "tm",
+            HitLevel::LowThrow => "tl",
+            HitLevel::Unblockable => "ub",
+        }
+    }
+}
+
+impl HitLevels {
+    /// Accepts both "," and "-" between hits, so users can write "m-l" for mid-low
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        if s.trim().is_empty() {
+            return Err(ParseError::new(s, "value is empty"));
+        }
+
+        s.split([',', '-'])
+            .map(|level| {
+                HitLevel::parse(level)
+                    .ok_or_else(|| ParseError::new(s, format!("unknown hit level \"{level}\"")))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(HitLevels)
+    }
+
+    /// 1-based numbers of the hits that are lows, e.g. [3] for "h,m,l"
+    pub fn low_hits(&self) -> Vec<usize> {
+        self.hits_where(HitLevel::is_low)
+    }
+
+    /// 1-based numbers of the hits that can be ducked
+    pub fn duckable_hits(&self) -> Vec<usize> {
+        self.hits_where(HitLevel::is_duckable)
+    }
+
+    pub fn is_duckable(&self) -> bool {
+        self.0.iter().any(HitLevel::is_duckable)
+    }
+
+    fn hits_where(&self, predicate: impl Fn(&HitLevel) -> bool) -> Vec<usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, level)| predicate(level))
+            .map(|(i, _)| i + 1)
+            .collect()
+    }
+}
+
+impl Display for HitLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.notation())
+    }
+}
+
+impl Display for HitLevels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let levels = self
+            .0
+            .iter()
+            .map(HitLevel::notation)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        write!(f, "{levels}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    use HitLevel::*;
+
+    #[rstest]
+    #[case("h", vec![High])]
+    #[case("h,m,l", vec![High, Mid, Low])]
+    #[case("m,th", vec![Mid, HighThrow])]
+    #[case("t", vec![HighThrow])]
+    #[case("sm, sl", vec![SpecialMid, SpecialLow])]
+    #[case("M,L", vec![Mid, Low])]
+    #[case("m!", vec![Unblockable])]
+    #[case("ub", vec![Unblockable])]
+    #[case("m-l", vec![Mid, Low])]
+    #[case("tm,tl", vec![MidThrow, LowThrow])]
+    fn test_parse(#[case] s: &str, #[case] expected: Vec<HitLevel>) {
+        assert_eq!(HitLevels::parse(s).unwrap(), HitLevels(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("h,,m")]
+    #[case("x")]
+    #[case("mid")]
+    fn test_parse_errors(#[case] s: &str) {
+        assert_eq!(HitLevels::parse(s).unwrap_err().value, s);
+    }
+
+    #[rstest]
+    #[case("h,m,l", true, vec![1], vec![3])]
+    #[case("m,m", false, vec![], vec![])]
+    #[case("l,h,l", true, vec![2], vec![1, 3])]
+    #[case("m,th", true, vec![2], vec![])]
+    #[case("sl", false, vec![], vec![1])]
+    fn test_duckable_and_lows(
+        #[case] s: &str,
+        #[case] duckable: bool,
+        #[case] duckable_hits: Vec<usize>,
+        #[case] low_hits: Vec<usize>,
+    ) {
+        let levels = HitLevels::parse(s).unwrap();
+
+        assert_eq!(levels.is_duckable(), duckable);
+        assert_eq!(levels.duckable_hits(), duckable_hits);
+        assert_eq!(levels.low_hits(), low_hits);
+    }
+
+    #[test]
+    fn test_display() {
+        let levels = HitLevels::parse("H, m, t").unwrap();
+
+        assert_eq!(levels.to_string(), "h,m,th");
+    }
+}
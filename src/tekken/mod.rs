@@ -1,4 +1,12 @@
 pub mod character;
 pub mod character_move;
+pub mod character_registry;
+pub mod crush;
+pub mod damage;
+pub mod frames;
+pub mod hit_level;
+pub mod parse_error;
+pub mod provenance;
+pub mod tracking;
 
 pub const TEKKEN_RED: u32 = 0xf50a64;
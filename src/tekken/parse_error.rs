@@ -0,0 +1,25 @@
+use std::{error::Error, fmt::Display};
+
+/// A move data value that couldn't be parsed, along with why
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub value: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(value: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not parse \"{}\": {}", self.value, self.reason)
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a move's data was loaded from
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Serialize, Deserialize)]
+pub enum MoveSource {
+    #[default]
+    Unknown,
+    Wavu,
+    /// A curated movelist file, by file name
+    File(String),
+}
+
+/// Where a move's data came from and how old it is, for settling disputes about a value
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Provenance {
+    pub source: MoveSource,
+    /// Wiki page the move is listed on, e.g. "Paul movelist"
+    pub page: Option<String>,
+    /// Revision of the page the data was read from
+    pub revision: Option<u64>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// When the community override applied to the move was last changed
+    pub overridden_at: Option<DateTime<Utc>>,
+}
+
+impl Provenance {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One line summary such as "Wavu · rev 123 · fetched 3h ago"
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let mut parts = Vec::new();
+        if self.source != MoveSource::Unknown {
+            parts.push(self.source.to_string());
+        }
+        if let Some(revision) = self.revision {
+            parts.push(format!("rev {revision}"));
+        }
+        if let Some(fetched_at) = self.fetched_at {
+            parts.push(format!("fetched {}", format_age(fetched_at, now)));
+        }
+        if let Some(overridden_at) = self.overridden_at {
+            parts.push(format!("corrected {}", format_age(overridden_at, now)));
+        }
+
+        parts.join(" · ")
+    }
+}
+
+impl Display for MoveSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveSource::Unknown => write!(f, "unknown"),
+            MoveSource::Wavu => write!(f, "Wavu"),
+            MoveSource::File(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Rounded down to the largest unit, e.g. "3h ago"
+fn format_age(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let age = now - at;
+
+    if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{}m ago", age.num_minutes())
+    } else {
+        "just now".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Duration::seconds(30), "just now")]
+    #[case(Duration::seconds(-30), "just now")]
+    #[case(Duration::minutes(5), "5m ago")]
+    #[case(Duration::minutes(3 * 60 + 59), "3h ago")]
+    #[case(Duration::days(2), "2d ago")]
+    fn test_format_age(#[case] age: Duration, #[case] expected: &str) {
+        let now = Utc::now();
+
+        assert_eq!(format_age(now - age, now), expected);
+    }
+
+    #[test]
+    fn test_summary() {
+        let now = Utc::now();
+        let provenance = Provenance {
+            source: MoveSource::Wavu,
+            page: Some("Paul movelist".into()),
+            revision: Some(123),
+            fetched_at: Some(now - Duration::hours(3)),
+            overridden_at: Some(now - Duration::days(2)),
+        };
+
+        assert_eq!(
+            provenance.summary(now),
+            "Wavu · rev 123 · fetched 3h ago · corrected 2d ago"
+        );
+    }
+
+    #[test]
+    fn test_summary_leaves_out_unknown_parts() {
+        let provenance = Provenance {
+            source: MoveSource::File("paul.toml".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(provenance.summary(Utc::now()), "paul.toml");
+        assert_eq!(Provenance::default().summary(Utc::now()), "");
+    }
+}
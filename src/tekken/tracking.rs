@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Whether a move catches the opponent sidestepping to either side. Sides are from the
+/// point of view of the player doing the sidestep, so a move that doesn't track left
+/// is beaten by sidestepping left (SSL)
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Serialize, Deserialize)]
+pub struct Tracking {
+    pub left: Option<TrackingSide>,
+    pub right: Option<TrackingSide>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum TrackingSide {
+    Tracks,
+    DoesNotTrack,
+    /// Anything more specific than yes/no, e.g. tracking that only kicks in late
+    Partial(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, poise::ChoiceParameter)]
+pub enum SidestepDirection {
+    Left,
+    Right,
+}
+
+impl Tracking {
+    pub fn new(left: Option<&str>, right: Option<&str>) -> Self {
+        Self {
+            left: left.and_then(TrackingSide::parse),
+            right: right.and_then(TrackingSide::parse),
+        }
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.left.is_some() || self.right.is_some()
+    }
+
+    pub fn side(&self, direction: SidestepDirection) -> Option<&TrackingSide> {
+        match direction {
+            SidestepDirection::Left => self.left.as_ref(),
+            SidestepDirection::Right => self.right.as_ref(),
+        }
+    }
+
+    /// Only moves that are known not to track count, unknown tracking doesn't
+    pub fn is_weak_to_sidestep(&self, direction: SidestepDirection) -> bool {
+        matches!(self.side(direction), Some(TrackingSide::DoesNotTrack))
+    }
+}
+
+impl TrackingSide {
+    /// Returns `None` for blank values, which means the tracking isn't known
+    pub fn parse(s: &str) -> Option<Self> {
+        let trimmed = s.trim();
+
+        match trimmed.to_ascii_lowercase().as_str() {
+            "" => None,
+            "y" | "yes" | "true" | "1" => Some(TrackingSide::Tracks),
+            "n" | "no" | "false" | "0" => Some(TrackingSide::DoesNotTrack),
+            _ => Some(TrackingSide::Partial(trimmed.into())),
+        }
+    }
+}
+
+impl Display for TrackingSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrackingSide::Tracks => write!(f, "tracks"),
+            TrackingSide::DoesNotTrack => write!(f, "weak to sidestep"),
+            TrackingSide::Partial(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Display for SidestepDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SidestepDirection::Left => write!(f, "left"),
+            SidestepDirection::Right => write!(f, "right"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("y", Some(TrackingSide::Tracks))]
+    #[case("Yes", Some(TrackingSide::Tracks))]
+    #[case("n", Some(TrackingSide::DoesNotTrack))]
+    #[case(" no ", Some(TrackingSide::DoesNotTrack))]
+    #[case("", None)]
+    #[case("  ", None)]
+    #[case("late", Some(TrackingSide::Partial("late".into())))]
+    fn test_parse_tracking_side(#[case] s: &str, #[case] expected: Option<TrackingSide>) {
+        assert_eq!(TrackingSide::parse(s), expected);
+    }
+
+    #[test]
+    fn test_weak_to_sidestep() {
+        let tracking = Tracking::new(Some("n"), Some("y"));
+
+        assert!(tracking.is_weak_to_sidestep(SidestepDirection::Left));
+        assert!(!tracking.is_weak_to_sidestep(SidestepDirection::Right));
+    }
+
+    #[test]
+    fn test_unknown_tracking_is_not_weak_to_sidestep() {
+        let tracking = Tracking::new(None, Some("late"));
+
+        assert!(tracking.is_known());
+        assert!(!tracking.is_weak_to_sidestep(SidestepDirection::Left));
+        assert!(!tracking.is_weak_to_sidestep(SidestepDirection::Right));
+    }
+}